@@ -0,0 +1,19 @@
+use rosu_memory_lib::init_loop;
+use rosu_memory_lib::reader::events::{EventPoller, GameEvent};
+use rosu_memory_lib::Error;
+
+fn main() -> Result<(), Error> {
+    let (state, process) = init_loop(500)?;
+    println!("Successfully initialized!");
+
+    // Polling happens on a background thread, events come back through a channel
+    let events = EventPoller::new(process, state).into_channel();
+    for event in events {
+        match event? {
+            GameEvent::BeatmapChanged { md5 } => println!("Beatmap changed: {md5}"),
+            GameEvent::ComboBroken { combo } => println!("Combo broken at {combo}x"),
+            event => println!("{event:?}"),
+        }
+    }
+    Ok(())
+}
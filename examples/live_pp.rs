@@ -0,0 +1,23 @@
+use rosu_memory_lib::init_loop;
+use rosu_memory_lib::reader::common::stable::memory::game_state;
+use rosu_memory_lib::reader::common::GameState;
+use rosu_memory_lib::reader::pp::LivePp;
+use rosu_memory_lib::Error;
+use std::time::Duration;
+
+fn main() -> Result<(), Error> {
+    let (mut state, process) = init_loop(500)?;
+    let mut live = LivePp::new();
+    loop {
+        if game_state(&process, &mut state)? == GameState::Playing {
+            match live.refresh(&process, &mut state) {
+                Ok(pp) => println!(
+                    "{:.2}pp (if FC: {:.2}pp, max: {:.2}pp, {:.2}*)",
+                    pp.current, pp.if_fc, pp.max, pp.current_stars
+                ),
+                Err(e) => println!("Error: {e:?}"),
+            }
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
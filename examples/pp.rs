@@ -1,7 +1,7 @@
 use rosu_mem::process::Process;
 use rosu_memory_lib::init_loop;
 use rosu_memory_lib::reader::beatmap::stable::file::path;
-use rosu_memory_lib::reader::common::stable::memory::menu_game_mode;
+use rosu_memory_lib::reader::common::stable::memory::{menu_mods, menu_ruleset};
 use rosu_memory_lib::reader::structs::State;
 use rosu_memory_lib::Error;
 use rosu_mods::GameModsLegacy;
@@ -26,7 +26,7 @@ use std::time::Duration;
 ///   Represents the current state of the PP calculator
 struct CalculatorState {
     current_pp: f64,
-    current_mods: i32,
+    current_mods: GameModsLegacy,
     current_beatmap: Beatmap,
     current_beatmap_path: PathBuf,
 }
@@ -36,20 +36,19 @@ impl CalculatorState {
     fn new() -> Self {
         Self {
             current_pp: 0.0,
-            current_mods: 0,
+            current_mods: GameModsLegacy::NoMod,
             current_beatmap: Beatmap::default(),
             current_beatmap_path: PathBuf::new(),
         }
     }
 
     /// Updates the mods if they have changed and returns whether an update occurred
-    fn update_mods(&mut self, new_mods: i32) -> bool {
+    fn update_mods(&mut self, new_mods: GameModsLegacy) -> bool {
         if new_mods != self.current_mods {
             self.current_mods = new_mods;
 
-            // Convert mod bits to human-readable format
-            let mods_readable = GameModsLegacy::from_bits(self.current_mods as u32).to_string();
-            println!("Mods: {mods_readable}");
+            // Displays as acronyms, e.g. HDDT
+            println!("Mods: {}", self.current_mods);
             true
         } else {
             false
@@ -81,7 +80,7 @@ impl CalculatorState {
     /// Calculates and updates PP if the value has changed
     fn update_pp(&mut self) {
         let diff_attrs = Difficulty::new()
-            .mods(self.current_mods as u32)
+            .mods(self.current_mods)
             .calculate(&self.current_beatmap);
 
         let new_pp = Performance::new(diff_attrs).calculate().pp();
@@ -102,9 +101,12 @@ fn process_game_state(
     match path(process, state) {
         Ok(beatmap_path) => {
             // Update mods if they changed
-            println!("Menu game mode: {}", menu_game_mode(process, state)?);
-            if let Ok(new_mods) = menu_game_mode(process, state) {
-                mods_changed = calc_state.update_mods(new_mods as i32);
+            println!(
+                "Menu game mode: {}",
+                menu_ruleset(process, state)?.to_string()
+            );
+            if let Ok(new_mods) = menu_mods(process, state) {
+                mods_changed = calc_state.update_mods(new_mods);
             }
 
             // Update beatmap if path changed and mods changed else it's useless to recalculate
@@ -0,0 +1,35 @@
+use rosu_memory_lib::init_loop;
+use rosu_memory_lib::memory::session::{SessionRecorder, SessionReplay};
+use rosu_memory_lib::reader::common::OsuClientKind;
+use rosu_memory_lib::reader::gameplay::GameplayReader;
+use rosu_memory_lib::Error;
+
+fn main() -> Result<(), Error> {
+    let (mut state, process) = init_loop(500)?;
+    let recorder = SessionRecorder::new(&process, &state.addresses);
+
+    // Record ~30 seconds of gameplay polling
+    for _ in 0..1000 {
+        let mut gameplay_reader = GameplayReader::new(&recorder, &mut state, OsuClientKind::Stable);
+        let _ = gameplay_reader.info();
+        recorder.next_frame();
+        std::thread::sleep(std::time::Duration::from_millis(30));
+    }
+    recorder.save("session.rmls")?;
+    println!("Recorded {} frames", recorder.frame_count());
+
+    // Play it back through the same readers
+    let mut replay = SessionReplay::load("session.rmls")?;
+    let mut state = replay.state();
+    loop {
+        let mut gameplay_reader = GameplayReader::new(&replay, &mut state, OsuClientKind::Stable);
+        match gameplay_reader.combo() {
+            Ok(combo) => println!("[{:?}] combo: {combo}", replay.timestamp()),
+            Err(e) => println!("[{:?}] Error: {e:?}", replay.timestamp()),
+        }
+        if !replay.next_frame() {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use rosu_memory_lib::reader::supervisor::Supervisor;
+use std::time::Duration;
+
+fn main() {
+    // Survives osu! being closed and reopened, reads fail while it's gone
+    let mut supervisor = Supervisor::new();
+    let connection = supervisor.subscribe();
+    loop {
+        match supervisor.read(|session| session.common().game_state()) {
+            Ok(state) => println!("Game state: {state:?}"),
+            Err(e) => println!("Error: {e}"),
+        }
+        for event in connection.try_iter() {
+            println!("{event:?}");
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,44 @@
+//! Serves osu! state to stream overlays, see `rosu_memory_lib::server`.
+//!
+//! ```text
+//! rosu-server [--address 127.0.0.1:24050] [--interval 100] [--static ./static]
+//! ```
+use std::net::SocketAddr;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use rosu_memory_lib::server::Server;
+
+fn main() -> ExitCode {
+    let mut server = Server::new().static_dir("static");
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args.next();
+        server = match (arg.as_str(), value) {
+            ("--address", Some(value)) => match value.parse::<SocketAddr>() {
+                Ok(address) => server.address(address),
+                Err(e) => return usage(&format!("invalid address {value}: {e}")),
+            },
+            ("--interval", Some(value)) => match value.parse() {
+                Ok(ms) => server.interval(Duration::from_millis(ms)),
+                Err(e) => return usage(&format!("invalid interval {value}: {e}")),
+            },
+            ("--static", Some(value)) => server.static_dir(value),
+            _ => return usage(&format!("unexpected argument {arg}")),
+        };
+    }
+
+    match server.run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage(error: &str) -> ExitCode {
+    eprintln!("{error}");
+    eprintln!("usage: rosu-server [--address 127.0.0.1:24050] [--interval <ms>] [--static <dir>]");
+    ExitCode::FAILURE
+}
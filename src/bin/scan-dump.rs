@@ -0,0 +1,87 @@
+//! Scans a memory dump for every osu! signature, or lists the addresses a
+//! recorded session resolved them to. Sessions only hold the data ranges
+//! readers went through, the code signatures live in can't be scanned there.
+//!
+//! ```text
+//! scan-dump <dump.bin> <base address>
+//! scan-dump <session.rmls>
+//! ```
+use std::process::ExitCode;
+
+use rosu_memory_lib::memory::dump::{MemoryDump, SignatureMatch};
+use rosu_memory_lib::memory::session::SessionReplay;
+use rosu_memory_lib::reader::structs::{SignatureStatus, StaticAddresses};
+use rosu_memory_lib::Error;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let found_all = match args.as_slice() {
+        [path] => SessionReplay::load(path).map(|replay| print_recorded(replay.addresses())),
+        [path, base] => parse_address(base)
+            .and_then(|base| MemoryDump::load(path, base))
+            .and_then(|dump| dump.scan())
+            .map(|report| print_report(&report)),
+        _ => {
+            eprintln!("usage: scan-dump <dump.bin> <base address>");
+            eprintln!("       scan-dump <session.rmls>");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match found_all {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_address(value: &str) -> Result<i32, Error> {
+    let digits = value.trim_start_matches("0x").trim_start_matches("0X");
+    u32::from_str_radix(digits, 16)
+        .map(|addr| addr as i32)
+        .map_err(|e| Error::Parse(format!("Invalid base address {value}: {e}")))
+}
+
+/// Prints every match, returns whether each signature matched exactly once
+fn print_report(report: &[SignatureMatch]) -> bool {
+    println!(
+        "{:<18} {:>7}  {:<10}  pattern",
+        "field", "matches", "address"
+    );
+    for m in report {
+        let address = m
+            .first()
+            .map(|addr| format!("{:#010x}", addr as u32))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<18} {:>7}  {:<10}  {}",
+            m.name,
+            m.count(),
+            address,
+            m.pattern
+        );
+    }
+    report.iter().all(|m| m.count() == 1)
+}
+
+/// Prints the addresses stored in a session, returns whether every signature
+/// was resolved while recording
+fn print_recorded(addresses: &StaticAddresses) -> bool {
+    println!("recorded signature addresses, sessions can't be scanned");
+    println!("{:<18} address", "field");
+    let report = addresses.report();
+    for (name, status) in &report {
+        let address = match status {
+            SignatureStatus::Resolved(addr) => format!("{:#010x}", *addr as u32),
+            SignatureStatus::Pending => "never resolved".to_string(),
+            SignatureStatus::Missing(e) => format!("missing: {e}"),
+        };
+        println!("{name:<18} {address}");
+    }
+    report
+        .iter()
+        .all(|(_, status)| matches!(status, SignatureStatus::Resolved(_)))
+}
@@ -12,6 +12,10 @@ pub enum Error {
     FileOperation(String),
     /// Error when an operation is not supported
     Unsupported(String),
+    /// Error when waiting took longer than allowed
+    Timeout(String),
+    /// Error when waiting was cancelled
+    Cancelled(String),
     /// Other general errors
     Other(String),
 }
@@ -26,6 +30,8 @@ impl fmt::Display for Error {
             Error::NotAvailable(msg) => write!(f, "Not available: {msg}"),
             Error::FileOperation(msg) => write!(f, "File operation error: {msg}"),
             Error::Unsupported(msg) => write!(f, "Unsupported operation: {msg}"),
+            Error::Timeout(msg) => write!(f, "Timed out: {msg}"),
+            Error::Cancelled(msg) => write!(f, "Cancelled: {msg}"),
             Error::Other(msg) => write!(f, "Error: {msg}"),
         }
     }
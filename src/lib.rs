@@ -1,8 +1,14 @@
 pub mod error;
+pub mod memory;
 pub mod reader;
+#[cfg(feature = "server")]
+pub mod server;
 
 pub use error::{Error, Result};
+pub use memory::MemorySource;
 pub use reader::init_loop;
+pub use reader::init_loop_with;
+pub use reader::session::OsuSession;
 pub use reader::waiting_for_gamestate;
 
 // Re-export commonly used items
@@ -0,0 +1,124 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::memory::MemorySource;
+use crate::reader::offsets::offsets;
+use crate::reader::structs::SignatureName;
+use crate::Error;
+use rosu_mem::signature::{find_signature, Signature};
+
+/// Raw memory dump mapped at a known base address.
+///
+/// Every reader accepts it in place of a `Process`, which makes it possible
+/// to look at a user's osu! memory without having osu! running.
+#[derive(Debug, Clone)]
+pub struct MemoryDump {
+    base: i32,
+    bytes: Vec<u8>,
+    executable_dir: Option<PathBuf>,
+}
+
+impl MemoryDump {
+    pub fn new(base: i32, bytes: Vec<u8>) -> Self {
+        Self {
+            base,
+            bytes,
+            executable_dir: None,
+        }
+    }
+
+    /// Loads a raw dump whose first byte was at `base` in the dumped process
+    pub fn load(path: impl AsRef<Path>, base: i32) -> Result<Self, Error> {
+        Ok(Self::new(base, std::fs::read(path)?))
+    }
+
+    pub fn with_executable_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.executable_dir = Some(dir.into());
+        self
+    }
+
+    pub fn base(&self) -> i32 {
+        self.base
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Scans the dump for every signature, see [`scan_signatures`]
+    pub fn scan(&self) -> Result<Vec<SignatureMatch>, Error> {
+        scan_signatures([(self.base, self.bytes.as_slice())])
+    }
+}
+
+impl MemorySource for MemoryDump {
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error> {
+        let bytes = usize::try_from(addr as i64 - self.base as i64)
+            .ok()
+            .and_then(|start| self.bytes.get(start..start + len))
+            .ok_or_else(|| {
+                Error::MemoryRead(format!(
+                    "Address {addr:#x}, length {len:#x} is outside of the dump"
+                ))
+            })?;
+        buff[..len].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error> {
+        find_signature(&self.bytes, sign)
+            .map(|offset| self.base + offset as i32)
+            .ok_or_else(|| Error::MemoryRead(format!("Cannot find signature {sign}")))
+    }
+
+    fn executable_dir(&self) -> Option<PathBuf> {
+        self.executable_dir.clone()
+    }
+}
+
+/// What scanning found for a single signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureMatch {
+    /// `StaticAddresses` field the signature resolves
+    pub name: SignatureName,
+    pub pattern: String,
+    /// Every address the pattern matched at, readers only ever use the first one
+    pub addresses: Vec<i32>,
+}
+
+impl SignatureMatch {
+    pub fn count(&self) -> usize {
+        self.addresses.len()
+    }
+
+    /// Address `StaticAddresses` would hold
+    pub fn first(&self) -> Option<i32> {
+        self.addresses.first().copied()
+    }
+}
+
+/// Scans `regions` (start address, bytes) for every signature of the current
+/// offset table, reporting all matches instead of stopping at the first one
+pub fn scan_signatures<'a>(
+    regions: impl IntoIterator<Item = (i32, &'a [u8])> + Clone,
+) -> Result<Vec<SignatureMatch>, Error> {
+    let table = offsets();
+    let mut report = Vec::new();
+    for (name, pattern) in table.signatures.entries() {
+        let signature = Signature::from_str(pattern)?;
+        let mut addresses = Vec::new();
+        for (base, bytes) in regions.clone() {
+            let mut offset = 0;
+            while let Some(found) = find_signature(&bytes[offset..], &signature) {
+                addresses.push(base + (offset + found) as i32);
+                offset += found + 1;
+            }
+        }
+        report.push(SignatureMatch {
+            name,
+            pattern: pattern.to_string(),
+            addresses,
+        });
+    }
+    Ok(report)
+}
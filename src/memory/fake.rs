@@ -0,0 +1,213 @@
+use std::path::PathBuf;
+
+use crate::memory::MemorySource;
+use crate::reader::offsets::offsets;
+use crate::reader::structs::StaticAddresses;
+use crate::Error;
+use rosu_mem::signature::{find_signature, Signature};
+
+/// First address handed out by the allocator, anything below is unmapped
+const ARENA_BASE: i32 = 0x0100_0000;
+/// Size of blocks created by [`FakeProcess::pointer_chain`]
+const CHAIN_BLOCK_SIZE: usize = 0x200;
+/// Bytes reserved around each placed signature so negative offsets stay mapped
+const SIGNATURE_PADDING: usize = 0x80;
+
+/// In-memory osu! process for deterministic tests.
+///
+/// Memory is a single arena starting at `0x01000000`, reading anything outside
+/// of what has been allocated fails like a bad address would on a live process.
+///
+/// # Examples
+///
+/// ```rust
+/// use rosu_memory_lib::memory::fake::FakeProcess;
+/// use rosu_memory_lib::reader::common::stable::offset::COMMON_OFFSET;
+/// use rosu_memory_lib::reader::common::{CommonReader, GameState, OsuClientKind};
+/// use rosu_memory_lib::reader::structs::{State, StaticAddresses};
+///
+/// let mut fake = FakeProcess::new();
+/// let addresses = fake.place_signatures();
+///
+/// let status = fake.pointer_chain(addresses.status, &[-COMMON_OFFSET.status]);
+/// fake.write_u32(status, GameState::Playing as u32);
+///
+/// let mut state = State {
+///     addresses: StaticAddresses::new(&fake).unwrap(),
+/// };
+/// let mut reader = CommonReader::new(&fake, &mut state, OsuClientKind::Stable);
+/// assert_eq!(reader.game_state().unwrap(), GameState::Playing);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FakeProcess {
+    memory: Vec<u8>,
+    executable_dir: Option<PathBuf>,
+}
+
+impl Default for FakeProcess {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeProcess {
+    pub fn new() -> Self {
+        Self {
+            memory: Vec::new(),
+            executable_dir: None,
+        }
+    }
+
+    pub fn with_executable_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.executable_dir = Some(dir.into());
+        self
+    }
+
+    /// Allocates `size` zeroed bytes and returns their address
+    pub fn alloc(&mut self, size: usize) -> i32 {
+        // keep every allocation 16 bytes aligned like the CLR does
+        let start = self.memory.len().next_multiple_of(0x10);
+        self.memory.resize(start + size, 0);
+        ARENA_BASE + start as i32
+    }
+
+    /// Overwrites memory at `addr`, panics if the range was never allocated
+    pub fn write(&mut self, addr: i32, bytes: &[u8]) {
+        let start = self
+            .index(addr, bytes.len())
+            .unwrap_or_else(|| panic!("write outside of fake memory at {addr:#x}"));
+        self.memory[start..start + bytes.len()].copy_from_slice(bytes);
+    }
+
+    pub fn write_i8(&mut self, addr: i32, value: i8) {
+        self.write(addr, &value.to_le_bytes());
+    }
+
+    pub fn write_u8(&mut self, addr: i32, value: u8) {
+        self.write(addr, &value.to_le_bytes());
+    }
+
+    pub fn write_i16(&mut self, addr: i32, value: i16) {
+        self.write(addr, &value.to_le_bytes());
+    }
+
+    pub fn write_i32(&mut self, addr: i32, value: i32) {
+        self.write(addr, &value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, addr: i32, value: u32) {
+        self.write(addr, &value.to_le_bytes());
+    }
+
+    pub fn write_i64(&mut self, addr: i32, value: i64) {
+        self.write(addr, &value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, addr: i32, value: u64) {
+        self.write(addr, &value.to_le_bytes());
+    }
+
+    pub fn write_f32(&mut self, addr: i32, value: f32) {
+        self.write(addr, &value.to_le_bytes());
+    }
+
+    pub fn write_f64(&mut self, addr: i32, value: f64) {
+        self.write(addr, &value.to_le_bytes());
+    }
+
+    /// Allocates a .NET string object and returns its address
+    pub fn alloc_string(&mut self, value: &str) -> i32 {
+        let utf16: Vec<u16> = value.encode_utf16().collect();
+        // C# string structure: 4B obj header, 4B str len, str itself
+        let addr = self.alloc(0x8 + utf16.len() * 2);
+        self.write_u32(addr + 0x4, utf16.len() as u32);
+        let bytes: Vec<u8> = utf16.iter().flat_map(|c| c.to_le_bytes()).collect();
+        self.write(addr + 0x8, &bytes);
+        addr
+    }
+
+    /// Allocates a .NET string and stores a pointer to it at `addr`
+    pub fn write_string(&mut self, addr: i32, value: &str) {
+        let string_addr = self.alloc_string(value);
+        self.write_i32(addr, string_addr);
+    }
+
+    /// Writes the bytes of a signature (wildcards as `00`) in a fresh padded
+    /// region and returns the address of its first byte
+    pub fn place_signature(&mut self, signature: &str) -> Result<i32, Error> {
+        let bytes = signature
+            .split(' ')
+            .map(|b| match b {
+                "??" => Ok(0),
+                _ => u8::from_str_radix(b, 16).map_err(|e| Error::Parse(e.to_string())),
+            })
+            .collect::<Result<Vec<u8>, Error>>()?;
+
+        let region = self.alloc(SIGNATURE_PADDING * 2 + bytes.len());
+        let addr = region + SIGNATURE_PADDING as i32;
+        self.write(addr, &bytes);
+        Ok(addr)
+    }
+
+    /// Places every signature osu! is scanned for and returns where each landed,
+    /// this is what `StaticAddresses::new` will resolve on this process
+    pub fn place_signatures(&mut self) -> StaticAddresses {
+        let mut addresses = StaticAddresses::default();
+        for (name, sig) in offsets().signatures.entries() {
+            // Safety: built-in signatures are always valid
+            let addr = self.place_signature(sig).unwrap();
+            addresses.set(name, addr);
+        }
+        addresses
+    }
+
+    /// Follows `offsets` from `addr` the way readers do (`read_i32(cur + offset)`),
+    /// allocating a zeroed block whenever a null pointer is met, and returns the
+    /// final address.
+    ///
+    /// Chains sharing a prefix end up sharing the same blocks, so building the
+    /// gameplay and result screen chains on the same `rulesets` address works.
+    pub fn pointer_chain(&mut self, addr: i32, offsets: &[i32]) -> i32 {
+        let mut cur = addr;
+        for offset in offsets {
+            let slot = cur + offset;
+            let next = MemorySource::read_i32(self, slot)
+                .unwrap_or_else(|_| panic!("pointer chain outside of fake memory at {slot:#x}"));
+            cur = if next == 0 {
+                let block = self.alloc(CHAIN_BLOCK_SIZE);
+                self.write_i32(slot, block);
+                block
+            } else {
+                next
+            };
+        }
+        cur
+    }
+
+    fn index(&self, addr: i32, len: usize) -> Option<usize> {
+        let start = usize::try_from(addr.checked_sub(ARENA_BASE)?).ok()?;
+        (start + len <= self.memory.len()).then_some(start)
+    }
+}
+
+impl MemorySource for FakeProcess {
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error> {
+        let start = self.index(addr, len).ok_or_else(|| {
+            Error::MemoryRead(format!(
+                "Trying to read bad address {addr:#x}, length {len:#x}"
+            ))
+        })?;
+        buff[..len].copy_from_slice(&self.memory[start..start + len]);
+        Ok(())
+    }
+
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error> {
+        find_signature(&self.memory, sign)
+            .map(|offset| ARENA_BASE + offset as i32)
+            .ok_or_else(|| Error::MemoryRead(format!("Cannot find signature {sign}")))
+    }
+
+    fn executable_dir(&self) -> Option<PathBuf> {
+        self.executable_dir.clone()
+    }
+}
@@ -0,0 +1,177 @@
+use std::path::PathBuf;
+
+use crate::Error;
+use rosu_mem::process::{Process, ProcessTraits};
+use rosu_mem::signature::Signature;
+
+pub mod dump;
+#[cfg(feature = "testing")]
+pub mod fake;
+pub mod session;
+
+macro_rules! prim_read_impl {
+    ($( $name:ident : $t:ty ),* $(,)?) => {
+        $(
+            fn $name(&self, addr: i32) -> Result<$t, Error> {
+                let mut bytes = [0u8; std::mem::size_of::<$t>()];
+                self.read(addr, std::mem::size_of::<$t>(), &mut bytes)?;
+                Ok(<$t>::from_le_bytes(bytes))
+            }
+        )*
+    };
+}
+
+/// Anything readers can pull osu! memory from.
+///
+/// Every reader in `reader::*` is generic over this trait so the backing memory
+/// can be swapped out (mocks, dumps, caching layers...) without touching the
+/// readers themselves. [`Process`] is the default implementation and is what
+/// [`crate::init_loop`] hands back.
+///
+/// Only [`MemorySource::read`] and [`MemorySource::read_signature`] are required,
+/// every typed read is built on top of `read` and can be overridden if the
+/// backend has a faster path.
+///
+/// Implementations must be `Sync` because signatures can be scanned in parallel
+/// (see the `parallel-read` feature), use a `Mutex` for any interior state.
+pub trait MemorySource: Sync {
+    /// Fills `buff` with `len` bytes starting at `addr`
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error>;
+
+    /// Returns the address of the first match of `sign`
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error>;
+
+    /// Directory containing the osu! executable, if known
+    fn executable_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Address `osu!.exe` is loaded at, if known
+    fn module_base(&self) -> Option<i32> {
+        None
+    }
+
+    /// Whether the backing process still exists, sources that can't tell
+    /// report `true` and let failing reads speak for themselves
+    fn is_alive(&self) -> bool {
+        true
+    }
+
+    prim_read_impl! {
+        read_i8: i8,
+        read_u8: u8,
+        read_i16: i16,
+        read_u16: u16,
+        read_i32: i32,
+        read_u32: u32,
+        read_i64: i64,
+        read_u64: u64,
+        read_f32: f32,
+        read_f64: f64,
+    }
+
+    /// Reads a .NET string through the pointer stored at `addr`
+    fn read_string(&self, addr: i32) -> Result<String, Error> {
+        let addr = self.read_i32(addr)?;
+        // C# string structure: 4B obj header, 4B str len, str itself
+        let len = self.read_u32(addr + 0x4)? as usize;
+
+        let mut bytes = vec![0u8; len * 2];
+        self.read(addr + 0x8, bytes.len(), &mut bytes)?;
+
+        let utf16: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        Ok(String::from_utf16_lossy(&utf16))
+    }
+}
+
+impl MemorySource for Process {
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error> {
+        Ok(ProcessTraits::read(self, addr, len, buff)?)
+    }
+
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error> {
+        Ok(ProcessTraits::read_signature(self, sign)?)
+    }
+
+    fn executable_dir(&self) -> Option<PathBuf> {
+        self.executable_dir.clone()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn module_base(&self) -> Option<i32> {
+        // wine maps the executable like any other file, its lowest mapping
+        // comes first
+        std::fs::read_to_string(format!("/proc/{}/maps", self.pid))
+            .ok()?
+            .lines()
+            .find(|line| line.ends_with("osu!.exe"))
+            .and_then(|line| line.split('-').next())
+            .and_then(|from| u32::from_str_radix(from, 16).ok())
+            .map(|from| from as i32)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn module_base(&self) -> Option<i32> {
+        use windows::Win32::Foundation::HMODULE;
+        use windows::Win32::System::ProcessStatus::EnumProcessModules;
+
+        // the executable is always the first module
+        let mut module = HMODULE::default();
+        let mut needed = 0u32;
+        // Safety: the handle is owned by `self` and `module` has room for the
+        // one module asked for
+        let listed = unsafe {
+            EnumProcessModules(
+                self.handle,
+                &mut module,
+                std::mem::size_of::<HMODULE>() as u32,
+                &mut needed,
+            )
+        }
+        .as_bool();
+        (listed && needed > 0).then_some(module.0 as i32)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn is_alive(&self) -> bool {
+        std::path::Path::new(&format!("/proc/{}", self.pid)).exists()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn is_alive(&self) -> bool {
+        use windows::Win32::Foundation::STILL_ACTIVE;
+        use windows::Win32::System::Threading::GetExitCodeProcess;
+
+        // the handle is only opened with query and read access, which is
+        // enough for the exit code but not for waiting on it
+        let mut exit_code = 0u32;
+        // Safety: the handle is owned by `self` and only closed on drop
+        let queried = unsafe { GetExitCodeProcess(self.handle, &mut exit_code) }.as_bool();
+        queried && exit_code == STILL_ACTIVE.0 as u32
+    }
+}
+
+impl<T: MemorySource + ?Sized> MemorySource for &T {
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error> {
+        (**self).read(addr, len, buff)
+    }
+
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error> {
+        (**self).read_signature(sign)
+    }
+
+    fn executable_dir(&self) -> Option<PathBuf> {
+        (**self).executable_dir()
+    }
+
+    fn module_base(&self) -> Option<i32> {
+        (**self).module_base()
+    }
+
+    fn is_alive(&self) -> bool {
+        (**self).is_alive()
+    }
+}
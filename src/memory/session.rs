@@ -0,0 +1,390 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::memory::MemorySource;
+use crate::reader::offsets::offsets;
+use crate::reader::structs::{State, StaticAddresses, SIGNATURES};
+use crate::Error;
+use rosu_mem::signature::Signature;
+
+const MAGIC: &[u8; 4] = b"RMLS";
+const VERSION: u32 = 1;
+/// Timestamp and chunk count, the smallest a recorded frame can be
+const FRAME_HEADER_SIZE: u64 = 12;
+
+/// A captured memory range keyed by `(address, length)`
+type Chunks = BTreeMap<(i32, usize), Vec<u8>>;
+
+/// Latest capture of every range with the frame it comes from, ordered by
+/// address so the captures covering a read are next to each other
+type View = BTreeMap<(i32, usize), (usize, Vec<u8>)>;
+
+#[derive(Debug, Clone, Default)]
+struct Frame {
+    timestamp: Duration,
+    chunks: Chunks,
+}
+
+/// Records every read going through it so the session can be replayed later
+/// with [`SessionReplay`].
+///
+/// Reads are grouped in frames, call [`SessionRecorder::next_frame`] once per
+/// polling tick. A range is only stored again when its content changed since
+/// the last time it was captured, which keeps recordings small.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rosu_memory_lib::init_loop;
+/// use rosu_memory_lib::memory::session::SessionRecorder;
+/// use rosu_memory_lib::reader::gameplay::GameplayReader;
+/// use rosu_memory_lib::reader::common::OsuClientKind;
+///
+/// let (mut state, process) = init_loop(500)?;
+/// let recorder = SessionRecorder::new(&process, &state.addresses);
+/// for _ in 0..100 {
+///     let _ = GameplayReader::new(&recorder, &mut state, OsuClientKind::Stable).info();
+///     recorder.next_frame();
+/// }
+/// recorder.save("session.rmls")?;
+/// # Ok::<(), rosu_memory_lib::Error>(())
+/// ```
+pub struct SessionRecorder<P: MemorySource> {
+    source: P,
+    start: Instant,
+    inner: Mutex<RecorderState>,
+}
+
+#[derive(Default)]
+struct RecorderState {
+    /// Signatures resolved before or during the recording
+    addresses: StaticAddresses,
+    frames: Vec<Frame>,
+    current: Frame,
+    last: HashMap<(i32, usize), Vec<u8>>,
+}
+
+impl<P: MemorySource> SessionRecorder<P> {
+    pub fn new(source: P, addresses: &StaticAddresses) -> Self {
+        Self {
+            source,
+            start: Instant::now(),
+            inner: Mutex::new(RecorderState {
+                addresses: addresses.clone(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Closes the current frame and starts a new one
+    pub fn next_frame(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let frame = std::mem::take(&mut inner.current);
+        inner.frames.push(frame);
+        inner.current.timestamp = self.start.elapsed();
+    }
+
+    /// Number of closed frames
+    pub fn frame_count(&self) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .frames
+            .len()
+    }
+
+    /// Writes every closed frame to `path`, the frame in progress is included
+    /// if it captured anything
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let mut w = BufWriter::new(File::create(path)?);
+
+        w.write_all(MAGIC)?;
+        w.write_all(&VERSION.to_le_bytes())?;
+        for (name, _) in SIGNATURES.entries() {
+            w.write_all(&inner.addresses.get(name).to_le_bytes())?;
+        }
+
+        let pending = (!inner.current.chunks.is_empty()).then_some(&inner.current);
+        let frames: Vec<&Frame> = inner.frames.iter().chain(pending).collect();
+        w.write_all(&(frames.len() as u32).to_le_bytes())?;
+        for frame in frames {
+            w.write_all(&(frame.timestamp.as_millis() as u64).to_le_bytes())?;
+            w.write_all(&(frame.chunks.len() as u32).to_le_bytes())?;
+            for ((addr, len), bytes) in &frame.chunks {
+                w.write_all(&addr.to_le_bytes())?;
+                w.write_all(&(*len as u32).to_le_bytes())?;
+                w.write_all(bytes)?;
+            }
+        }
+        w.flush()?;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> P {
+        self.source
+    }
+}
+
+impl<P: MemorySource> MemorySource for SessionRecorder<P> {
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error> {
+        self.source.read(addr, len, buff)?;
+
+        let key = (addr, len);
+        let bytes = &buff[..len];
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.last.get(&key).is_some_and(|last| last == bytes) {
+            return Ok(());
+        }
+        inner.last.insert(key, bytes.to_vec());
+        inner.current.chunks.insert(key, bytes.to_vec());
+        Ok(())
+    }
+
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error> {
+        let addr = self.source.read_signature(sign)?;
+
+        // lazily resolved signatures still have to end up in the recording
+        if let Some(name) = offsets().signatures.name_of(sign) {
+            self.inner
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .addresses
+                .set(name, addr);
+        }
+        Ok(addr)
+    }
+
+    fn executable_dir(&self) -> Option<PathBuf> {
+        self.source.executable_dir()
+    }
+
+    fn module_base(&self) -> Option<i32> {
+        self.source.module_base()
+    }
+
+    fn is_alive(&self) -> bool {
+        self.source.is_alive()
+    }
+}
+
+/// Plays back a session captured by [`SessionRecorder`].
+///
+/// Reads are served from the memory as it was at the current frame, anything
+/// that was never captured fails with [`Error::MemoryRead`] just like an unmapped
+/// address would.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rosu_memory_lib::memory::session::SessionReplay;
+/// use rosu_memory_lib::reader::gameplay::GameplayReader;
+/// use rosu_memory_lib::reader::common::OsuClientKind;
+///
+/// let mut replay = SessionReplay::load("session.rmls")?;
+/// let mut state = replay.state();
+/// loop {
+///     let combo = GameplayReader::new(&replay, &mut state, OsuClientKind::Stable).combo();
+///     println!("{:?} {combo:?}", replay.timestamp());
+///     if !replay.next_frame() {
+///         break;
+///     }
+/// }
+/// # Ok::<(), rosu_memory_lib::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SessionReplay {
+    addresses: StaticAddresses,
+    frames: Vec<Frame>,
+    position: usize,
+    view: View,
+    /// Longest capture in `view`, bounds how far back a lookup searches
+    max_len: usize,
+    executable_dir: Option<PathBuf>,
+}
+
+impl SessionReplay {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file = File::open(path)?;
+        // counts and lengths come from the file, nothing bigger than it is
+        // allocated up front
+        let size = file.metadata()?.len();
+        let mut r = BufReader::new(file);
+
+        let mut magic = [0u8; 4];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(Error::Parse("Not a recorded session".to_string()));
+        }
+        let version = read_u32(&mut r)?;
+        if version != VERSION {
+            return Err(Error::Unsupported(format!(
+                "Session format version {version}"
+            )));
+        }
+
+        let mut addresses = StaticAddresses::default();
+        for (name, _) in SIGNATURES.entries() {
+            // 0 means the signature was never resolved while recording
+            match read_u32(&mut r)? as i32 {
+                0 => {}
+                addr => addresses.set(name, addr),
+            }
+        }
+
+        let frame_count = read_u32(&mut r)?;
+        let mut frames =
+            Vec::with_capacity(u64::from(frame_count).min(size / FRAME_HEADER_SIZE) as usize);
+        for _ in 0..frame_count {
+            let timestamp = Duration::from_millis(read_u64(&mut r)?);
+            let chunk_count = read_u32(&mut r)?;
+            let mut chunks = Chunks::new();
+            for _ in 0..chunk_count {
+                let addr = read_u32(&mut r)? as i32;
+                let len = read_u32(&mut r)? as usize;
+                let mut bytes = Vec::new();
+                (&mut r).take(len as u64).read_to_end(&mut bytes)?;
+                if bytes.len() != len {
+                    return Err(Error::Parse("Truncated session".to_string()));
+                }
+                chunks.insert((addr, len), bytes);
+            }
+            frames.push(Frame { timestamp, chunks });
+        }
+
+        let mut replay = Self {
+            addresses,
+            frames,
+            position: 0,
+            view: View::new(),
+            max_len: 0,
+            executable_dir: None,
+        };
+        replay.seek(0);
+        Ok(replay)
+    }
+
+    /// Songs folder resolution needs the executable directory of the
+    /// recorded machine, set it here when replaying file based readers
+    pub fn with_executable_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.executable_dir = Some(dir.into());
+        self
+    }
+
+    /// Addresses resolved when the session was recorded
+    pub fn addresses(&self) -> &StaticAddresses {
+        &self.addresses
+    }
+
+    /// A fresh `State` pointing at the recorded addresses
+    pub fn state(&self) -> State {
+        State {
+            addresses: self.addresses.clone(),
+        }
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Time elapsed since the start of the recording at the current frame
+    pub fn timestamp(&self) -> Duration {
+        self.frames
+            .get(self.position)
+            .map(|f| f.timestamp)
+            .unwrap_or_default()
+    }
+
+    /// Moves to the next frame, returns `false` once the end is reached
+    pub fn next_frame(&mut self) -> bool {
+        if self.position + 1 >= self.frames.len() {
+            return false;
+        }
+        self.position += 1;
+        self.apply(self.position);
+        true
+    }
+
+    /// Jumps to `frame`, clamped to the last frame
+    pub fn seek(&mut self, frame: usize) {
+        self.position = frame.min(self.frames.len().saturating_sub(1));
+        self.view.clear();
+        self.max_len = 0;
+        for index in 0..self.frames.len().min(self.position + 1) {
+            self.apply(index);
+        }
+    }
+
+    fn apply(&mut self, index: usize) {
+        for (key, bytes) in &self.frames[index].chunks {
+            self.max_len = self.max_len.max(key.1);
+            self.view.insert(*key, (index, bytes.clone()));
+        }
+    }
+
+    /// Every captured range as of the current frame, as `(address, bytes)`
+    pub fn regions(&self) -> impl Iterator<Item = (i32, &[u8])> + Clone {
+        self.view
+            .iter()
+            .map(|((addr, _), (_, bytes))| (*addr, bytes.as_slice()))
+    }
+
+    /// Newest capture covering the range, only captures starting at most
+    /// `max_len` bytes before `addr` can cover it
+    fn lookup(&self, addr: i32, len: usize) -> Option<&[u8]> {
+        let from = addr.saturating_sub(self.max_len as i32);
+        self.view
+            .range((from, 0)..=(addr, usize::MAX))
+            .filter(|((start, size), _)| *start as i64 + *size as i64 >= addr as i64 + len as i64)
+            .max_by_key(|(_, (frame, _))| *frame)
+            .map(|((start, _), (_, bytes))| {
+                let offset = (addr - start) as usize;
+                &bytes[offset..offset + len]
+            })
+    }
+}
+
+impl MemorySource for SessionReplay {
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error> {
+        let bytes = self.lookup(addr, len).ok_or_else(|| {
+            Error::MemoryRead(format!(
+                "Address {addr:#x}, length {len:#x} was not recorded"
+            ))
+        })?;
+        buff[..len].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error> {
+        offsets()
+            .signatures
+            .name_of(sign)
+            .map(|name| self.addresses.get(name))
+            .filter(|addr| *addr != 0)
+            .ok_or_else(|| Error::NotAvailable(format!("Signature {sign} was not recorded")))
+    }
+
+    fn executable_dir(&self) -> Option<PathBuf> {
+        self.executable_dir.clone()
+    }
+}
+
+fn read_u32(r: &mut impl Read) -> Result<u32, Error> {
+    let mut bytes = [0u8; 4];
+    r.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(r: &mut impl Read) -> Result<u64, Error> {
+    let mut bytes = [0u8; 8];
+    r.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
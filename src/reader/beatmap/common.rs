@@ -1,6 +1,8 @@
 use crate::common::GameMode;
+use rosu_mods::GameModsLegacy;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeatmapInfo {
     pub metadata: BeatmapMetadata,
     pub location: BeatmapLocation,
@@ -9,6 +11,7 @@ pub struct BeatmapInfo {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeatmapMetadata {
     pub author: String,
     pub creator: String,
@@ -18,6 +21,7 @@ pub struct BeatmapMetadata {
     pub tags: String,
 }
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeatmapTechnicalInfo {
     pub md5: String,
     pub id: i32,
@@ -26,6 +30,7 @@ pub struct BeatmapTechnicalInfo {
     pub ranked_status: BeatmapStatus,
 }
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeatmapLocation {
     pub folder: String,
     pub filename: String,
@@ -46,13 +51,17 @@ impl BeatmapLocation {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeatmapStarRating {
     pub no_mod: f64,
     pub dt: f64,
     pub ht: f64,
+    /// With the mods selected in the menu
+    pub selected: f64,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeatmapStats {
     pub ar: f32,
     pub od: f32,
@@ -64,7 +73,54 @@ pub struct BeatmapStats {
     pub slider_count: i32,
 }
 
+/// Beatmap stats with mods applied, durations are in real time milliseconds
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BeatmapModdedStats {
+    pub mode: GameMode,
+    pub mods: GameModsLegacy,
+    pub clock_rate: f64,
+    pub ar: f64,
+    pub od: f64,
+    /// Key count in osu!mania
+    pub cs: f64,
+    pub hp: f64,
+    /// Most common bpm
+    pub bpm: f64,
+    pub bpm_min: f64,
+    pub bpm_max: f64,
+    /// Until the end of the last hit object
+    pub length: i32,
+    /// From the first to the end of the last hit object, without breaks
+    pub drain_time: i32,
+    pub hit_windows: BeatmapHitWindows,
+}
+
+/// Hit windows in real time milliseconds, only `preempt` is set in osu!catch
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BeatmapHitWindows {
+    /// Time an object is shown before it has to be hit, from AR
+    pub preempt: f64,
+    /// Window for a rainbow 300 (geki), only in osu!mania
+    pub perfect: Option<f64>,
+    /// Window for a 300
+    pub great: f64,
+    /// Window for a 200 (katu), only in osu!mania
+    pub good: Option<f64>,
+    /// Window for a 100
+    pub ok: Option<f64>,
+    /// Window for a 50, not in osu!taiko
+    pub meh: Option<f64>,
+}
+
+/// Serialized as its `to_string()` value
 #[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 #[repr(i16)]
 pub enum BeatmapStatus {
     #[default]
@@ -115,7 +171,13 @@ impl From<i32> for BeatmapStatus {
     }
 }
 
-pub(crate) struct BeatmapOffset {
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct BeatmapOffset {
     pub ptr: i32,
     pub metadata: BeatmapMetadataOffset,
     pub location: BeatmapLocationOffset,
@@ -123,6 +185,12 @@ pub(crate) struct BeatmapOffset {
     pub technical: BeatmapTechnicalOffset,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct BeatmapStatsOffset {
     pub ar: i32,
     pub od: i32,
@@ -135,6 +203,12 @@ pub struct BeatmapStatsOffset {
     pub slider_count: i32,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct BeatmapLocationOffset {
     pub folder: i32,
     pub filename: i32,
@@ -142,6 +216,12 @@ pub struct BeatmapLocationOffset {
     pub cover: i32,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct BeatmapTechnicalOffset {
     pub md5: i32,
     pub id: i32,
@@ -150,6 +230,12 @@ pub struct BeatmapTechnicalOffset {
     pub ranked_status: i32,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct BeatmapMetadataOffset {
     pub author: i32,
     pub creator: i32,
@@ -0,0 +1,314 @@
+//! Difficulty attributes of a beatmap for any mods, calculated with rosu-pp.
+//!
+//! Results are cached process-wide by beatmap md5, mode, mods and clock rate, so
+//! polling the star rating of the selected beatmap only parses the `.osu`
+//! file again when the beatmap or the mods change. [`modded_stats`] gives the
+//! AR/OD/CS/HP, bpm and durations the mods result in.
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use crate::reader::beatmap::common::{BeatmapHitWindows, BeatmapModdedStats};
+use crate::reader::common::GameMode;
+use crate::reader::pp::ruleset;
+use crate::Error;
+use rosu_mods::GameModsLegacy;
+use rosu_pp::any::DifficultyAttributes;
+use rosu_pp::model::hit_object::{HitObjectKind, HoldNote, Spinner};
+use rosu_pp::model::mode::GameMode as PpMode;
+use rosu_pp::{Beatmap, Difficulty};
+
+/// Entries kept before the oldest ones are dropped
+pub const CACHE_CAPACITY: usize = 64;
+
+type Entry = (DifficultyKey, DifficultyAttributes);
+
+static CACHE: LazyLock<Mutex<VecDeque<Entry>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(CACHE_CAPACITY)));
+
+/// Mods to calculate difficulty for, with an optional custom clock rate
+/// overriding the one implied by DT/NC/HT and an optional mode osu!standard
+/// beatmaps are converted to
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DifficultyMods {
+    pub mods: GameModsLegacy,
+    pub clock_rate: Option<f64>,
+    pub mode: Option<GameMode>,
+}
+
+impl DifficultyMods {
+    pub fn new(mods: GameModsLegacy) -> Self {
+        Self {
+            mods,
+            clock_rate: None,
+            mode: None,
+        }
+    }
+
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+        self
+    }
+
+    /// Calculates on the beatmap as played in `mode`, see [`as_played`]
+    pub fn mode(mut self, mode: GameMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    fn calculate(&self, map: &Beatmap) -> DifficultyAttributes {
+        match self.mode {
+            Some(mode) => self
+                .difficulty()
+                .calculate(&as_played(map, mode, self.mods)),
+            None => self.difficulty().calculate(map),
+        }
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        let difficulty = Difficulty::new().mods(self.mods).lazer(false);
+        match self.clock_rate {
+            Some(clock_rate) => difficulty.clock_rate(clock_rate),
+            None => difficulty,
+        }
+    }
+}
+
+impl From<GameModsLegacy> for DifficultyMods {
+    fn from(mods: GameModsLegacy) -> Self {
+        Self::new(mods)
+    }
+}
+
+impl From<u32> for DifficultyMods {
+    fn from(bits: u32) -> Self {
+        Self::new(GameModsLegacy::from_bits(bits))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DifficultyKey {
+    md5: String,
+    mode: Option<GameMode>,
+    mods: u32,
+    clock_rate: Option<u64>,
+}
+
+impl DifficultyKey {
+    fn new(md5: &str, mods: &DifficultyMods) -> Self {
+        Self {
+            md5: md5.to_string(),
+            mode: mods.mode,
+            mods: mods.mods.bits(),
+            clock_rate: mods.clock_rate.map(f64::to_bits),
+        }
+    }
+}
+
+/// Difficulty attributes of the beatmap at `path` whose md5 is `md5`
+pub fn attributes(
+    md5: &str,
+    path: impl AsRef<Path>,
+    mods: impl Into<DifficultyMods>,
+) -> Result<DifficultyAttributes, Error> {
+    let mut attributes = attributes_many(md5, path, &[mods.into()])?;
+    Ok(attributes.remove(0))
+}
+
+/// Same as [`attributes`] for several mod combinations, in the same order.
+///
+/// The beatmap is parsed at most once, and not at all if every combination
+/// is cached already.
+pub fn attributes_many(
+    md5: &str,
+    path: impl AsRef<Path>,
+    mods: &[DifficultyMods],
+) -> Result<Vec<DifficultyAttributes>, Error> {
+    let keys: Vec<_> = mods.iter().map(|m| DifficultyKey::new(md5, m)).collect();
+    let mut found: Vec<_> = {
+        let cache = lock();
+        keys.iter()
+            .map(|key| {
+                cache
+                    .iter()
+                    .find(|(cached, _)| cached == key)
+                    .map(|(_, attributes)| attributes.clone())
+            })
+            .collect()
+    };
+    if found.iter().all(Option::is_some) {
+        return Ok(found.into_iter().flatten().collect());
+    }
+
+    let map = load(path.as_ref())?;
+
+    let mut calculated = Vec::new();
+    for ((slot, key), mods) in found.iter_mut().zip(keys).zip(mods) {
+        if slot.is_none() {
+            let attributes = mods.calculate(&map);
+            calculated.push((key, attributes.clone()));
+            *slot = Some(attributes);
+        }
+    }
+
+    let mut cache = lock();
+    for entry in calculated {
+        if !cache.iter().any(|(key, _)| *key == entry.0) {
+            if cache.len() == CACHE_CAPACITY {
+                cache.pop_front();
+            }
+            cache.push_back(entry);
+        }
+    }
+    Ok(found.into_iter().flatten().collect())
+}
+
+/// AR/OD/CS/HP, bpm, durations and hit windows of the beatmap at `path`
+/// played in `mode` with `mods` applied, see [`as_played`] for converts
+pub fn modded_stats(
+    path: impl AsRef<Path>,
+    mode: GameMode,
+    mods: impl Into<DifficultyMods>,
+) -> Result<BeatmapModdedStats, Error> {
+    Ok(modded_stats_of(&load(path.as_ref())?, mode, &mods.into()))
+}
+
+/// `map` the way osu! plays it in `mode`: osu!standard beatmaps are
+/// converted, beatmaps of the other modes are always played in their own
+pub fn as_played<'a>(map: &'a Beatmap, mode: GameMode, mods: GameModsLegacy) -> Cow<'a, Beatmap> {
+    match ruleset(&mode) {
+        Some(ruleset) if map.mode == PpMode::Osu && ruleset != PpMode::Osu => {
+            let mut converted = map.clone();
+            match converted.convert_mut(ruleset, &mods.into()) {
+                Ok(()) => Cow::Owned(converted),
+                Err(e) => {
+                    tracing::debug!(error = %e, ?mode, "beatmap kept in its own mode");
+                    Cow::Borrowed(map)
+                }
+            }
+        }
+        _ => Cow::Borrowed(map),
+    }
+}
+
+pub(crate) fn modded_stats_of(
+    map: &Beatmap,
+    mode: GameMode,
+    mods: &DifficultyMods,
+) -> BeatmapModdedStats {
+    let map = as_played(map, mode, mods.mods);
+    let builder = map.attributes().mods(mods.mods);
+    let attributes = match mods.clock_rate {
+        Some(clock_rate) => builder.clock_rate(clock_rate),
+        None => builder,
+    }
+    .build();
+    let clock_rate = attributes.clock_rate;
+
+    let (bpm_min, bpm_max) = map
+        .timing_points
+        .iter()
+        .map(|point| 60_000.0 / point.beat_len)
+        .fold((f64::MAX, 0.0f64), |(min, max), bpm| {
+            (min.min(bpm), max.max(bpm))
+        });
+    let bpm_min = if bpm_max > 0.0 { bpm_min } else { 0.0 };
+
+    let end = map.hit_objects.last().map_or(0.0, |last| match &last.kind {
+        HitObjectKind::Spinner(Spinner { duration })
+        | HitObjectKind::Hold(HoldNote { duration }) => last.start_time + duration,
+        _ => last.start_time,
+    });
+    let start = map
+        .hit_objects
+        .first()
+        .map_or(0.0, |first| first.start_time);
+    let drain_time = (end - start - map.total_break_time()).max(0.0);
+
+    let mode = GameMode::from(map.mode as u32);
+    let windows = attributes.hit_windows;
+    let hit_windows = match mode {
+        // Fruits are caught, not timed
+        GameMode::Catch => BeatmapHitWindows {
+            preempt: windows.ar,
+            ..Default::default()
+        },
+        GameMode::Mania => mania_hit_windows(&map, mods.mods, clock_rate),
+        _ => BeatmapHitWindows {
+            preempt: windows.ar,
+            great: windows.od_great,
+            ok: windows.od_ok,
+            meh: windows.od_meh,
+            ..Default::default()
+        },
+    };
+
+    BeatmapModdedStats {
+        mode,
+        mods: mods.mods,
+        clock_rate,
+        ar: attributes.ar,
+        od: attributes.od,
+        // HR and EZ don't change the key count
+        cs: match mode {
+            GameMode::Mania => f64::from(map.cs),
+            _ => attributes.cs,
+        },
+        hp: attributes.hp,
+        bpm: map.bpm() * clock_rate,
+        bpm_min: bpm_min * clock_rate,
+        bpm_max: bpm_max * clock_rate,
+        length: (end / clock_rate) as i32,
+        drain_time: (drain_time / clock_rate) as i32,
+        hit_windows,
+    }
+}
+
+/// osu!stable judgement windows of osu!mania, converts don't depend on OD
+/// the same way. Notes come at the rate of the music so the windows stay the
+/// same in real time
+fn mania_hit_windows(map: &Beatmap, mods: GameModsLegacy, clock_rate: f64) -> BeatmapHitWindows {
+    let od = f64::from(map.od);
+    let [great, good, ok, meh] = if !map.is_convert {
+        let harder = 3.0 * (10.0 - od).clamp(0.0, 10.0);
+        [34.0, 67.0, 97.0, 121.0].map(|window| window + harder)
+    } else if od.round_ties_even() > 4.0 {
+        [34.0, 67.0, 97.0, 121.0]
+    } else {
+        [47.0, 77.0, 97.0, 121.0]
+    };
+    let window = |mut value: f64| {
+        if mods.contains(GameModsLegacy::HardRock) {
+            value /= 1.4;
+        } else if mods.contains(GameModsLegacy::Easy) {
+            value *= 1.4;
+        }
+        ((value * clock_rate).floor() / clock_rate).ceil()
+    };
+
+    BeatmapHitWindows {
+        preempt: 0.0,
+        perfect: Some(window(16.0)),
+        great: window(great),
+        good: Some(window(good)),
+        ok: Some(window(ok)),
+        meh: Some(window(meh)),
+    }
+}
+
+fn load(path: &Path) -> Result<Beatmap, Error> {
+    let map = Beatmap::from_path(path)?;
+    map.check_suspicion()
+        .map_err(|e| Error::Parse(format!("Suspicious beatmap {}: {e:?}", path.display())))?;
+    Ok(map)
+}
+
+/// Drops every cached result
+pub fn clear_cache() {
+    lock().clear();
+}
+
+fn lock() -> MutexGuard<'static, VecDeque<Entry>> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
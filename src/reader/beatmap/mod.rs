@@ -1,31 +1,32 @@
 pub mod common;
+pub mod difficulty;
 pub mod stable;
 
 use std::path::PathBuf;
 
 use crate::impl_osu_accessor;
+use crate::memory::MemorySource;
 use crate::reader::beatmap::common::BeatmapInfo;
+use crate::reader::beatmap::common::BeatmapModdedStats;
 use crate::reader::beatmap::common::BeatmapStarRating;
 use crate::reader::beatmap::common::BeatmapStats;
 use crate::reader::beatmap::common::BeatmapStatus;
+use crate::reader::beatmap::difficulty::DifficultyMods;
 use crate::reader::common::GameMode;
 use crate::reader::common::OsuClientKind;
-use crate::reader::structs::State;
+use crate::reader::structs::{State, StateHandle};
 use crate::Error;
 use rosu_mem::process::Process;
+use rosu_pp::any::DifficultyAttributes;
 
-pub struct BeatmapReader<'a> {
-    pub process: &'a Process,
-    pub state: &'a mut State,
+pub struct BeatmapReader<'a, P: MemorySource = Process, S: StateHandle = &'a mut State> {
+    pub process: &'a P,
+    pub state: S,
     pub osu_type: OsuClientKind,
 }
 
-impl<'a> BeatmapReader<'a> {
-    pub fn new(
-        p: &'a Process,
-        state: &'a mut State,
-        osu_type: OsuClientKind,
-    ) -> Result<Self, Error> {
+impl<'a, P: MemorySource, S: StateHandle> BeatmapReader<'a, P, S> {
+    pub fn new(p: &'a P, state: S, osu_type: OsuClientKind) -> Result<Self, Error> {
         Ok(Self {
             process: p,
             state,
@@ -62,5 +63,42 @@ impl<'a> BeatmapReader<'a> {
         fn path() -> PathBuf => stable::file::path,
         fn audio_path() -> PathBuf => stable::file::audio_path,
         fn star_rating() -> BeatmapStarRating => stable::file::star_rating,
+        fn modded_stats() -> BeatmapModdedStats => stable::file::modded_stats,
+    }
+
+    pub fn difficulty_attributes(
+        &mut self,
+        mods: impl Into<DifficultyMods>,
+    ) -> Result<DifficultyAttributes, Error> {
+        match self.osu_type {
+            OsuClientKind::Stable => {
+                let process = self.process;
+                self.state
+                    .with_state(|state| stable::file::difficulty_attributes(process, state, mods))
+            }
+            _ => Err(Error::Unsupported(
+                "Unsupported osu type for now".to_string(),
+            )),
+        }
+    }
+
+    pub fn modded_stats_with_mods(
+        &mut self,
+        mods: impl Into<DifficultyMods>,
+    ) -> Result<BeatmapModdedStats, Error> {
+        match self.osu_type {
+            OsuClientKind::Stable => {
+                let process = self.process;
+                self.state
+                    .with_state(|state| stable::file::modded_stats_with_mods(process, state, mods))
+            }
+            _ => Err(Error::Unsupported(
+                "Unsupported osu type for now".to_string(),
+            )),
+        }
+    }
+
+    pub fn star_rating_with_mods(&mut self, mods: impl Into<DifficultyMods>) -> Result<f64, Error> {
+        Ok(self.difficulty_attributes(mods)?.stars())
     }
 }
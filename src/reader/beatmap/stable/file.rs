@@ -1,20 +1,24 @@
 use std::path::PathBuf;
 
 use crate::common::GameMode;
+use crate::memory::MemorySource;
 use crate::reader::beatmap::common::{
-    BeatmapInfo, BeatmapLocation, BeatmapMetadata, BeatmapStarRating, BeatmapStats, BeatmapStatus,
-    BeatmapTechnicalInfo,
+    BeatmapInfo, BeatmapLocation, BeatmapMetadata, BeatmapModdedStats, BeatmapStarRating,
+    BeatmapStats, BeatmapStatus, BeatmapTechnicalInfo,
 };
+use crate::reader::beatmap::difficulty::{self, DifficultyMods};
+use crate::reader::beatmap::stable::beatmap_addr;
 use crate::reader::beatmap::stable::memory::{audio, filename, folder};
-use crate::reader::beatmap::stable::{beatmap_addr, offset::BEATMAP_OFFSET};
-use crate::reader::common::stable::memory::path_folder;
+use crate::reader::common::stable::memory::{menu_mods, menu_ruleset, path_folder};
+use crate::reader::offsets::offsets;
 use crate::reader::structs::State;
 use crate::Error;
 use rosu_map::section::hit_objects::HitObjectKind;
 use rosu_map::Beatmap as RmBeatmap;
-use rosu_mem::process::{Process, ProcessTraits};
+use rosu_mods::GameModsLegacy;
+use rosu_pp::any::DifficultyAttributes;
 
-pub fn path(p: &Process, state: &mut State) -> Result<PathBuf, Error> {
+pub fn path<P: MemorySource>(p: &P, state: &mut State) -> Result<PathBuf, Error> {
     let folder = folder(p, state)?;
     let filename = filename(p, state)?;
     let songs_path = path_folder(p, state)?;
@@ -22,7 +26,7 @@ pub fn path(p: &Process, state: &mut State) -> Result<PathBuf, Error> {
     Ok(songs_path.join(folder).join(filename))
 }
 
-pub fn audio_path(p: &Process, state: &mut State) -> Result<PathBuf, Error> {
+pub fn audio_path<P: MemorySource>(p: &P, state: &mut State) -> Result<PathBuf, Error> {
     let folder = folder(p, state)?;
     let audio = audio(p, state)?;
     let songs_path = path_folder(p, state)?;
@@ -36,7 +40,7 @@ macro_rules! generate_beatmap_field_getter {
         $( $fn_name:ident : $ret_ty:ty = $field:ident ; )*
     ) => {
         $(
-            pub fn $fn_name(p: &Process, state: &mut State) -> Result<$ret_ty, Error> {
+            pub fn $fn_name<P: MemorySource>(p: &P, state: &mut State) -> Result<$ret_ty, Error> {
                 let path = path(p, state)?;
                 let b = RmBeatmap::from_path(path)?;
                 Ok(b.$field)
@@ -50,7 +54,7 @@ macro_rules! generate_beatmap_custom_getter_safe {
         $( $fn_name:ident : $ret_ty:ty = |$b:ident| $body:block )*
     ) => {
         $(
-            pub fn $fn_name(p: &Process, state: &mut State) -> Result<$ret_ty, Error> {
+            pub fn $fn_name<P: MemorySource>(p: &P, state: &mut State) -> Result<$ret_ty, Error> {
                 let path = path(p, state)?;
                 let $b = RmBeatmap::from_path(path)?;
                 $body
@@ -111,31 +115,90 @@ generate_beatmap_custom_getter_safe! {
 }
 
 // cant do this in file mode
-pub fn status(p: &Process, state: &mut State) -> Result<BeatmapStatus, Error> {
+pub fn status<P: MemorySource>(p: &P, state: &mut State) -> Result<BeatmapStatus, Error> {
     // cant do this in file mode
     crate::reader::beatmap::stable::memory::status(p, state)
 }
 
-pub fn star_rating(p: &Process, state: &mut State) -> Result<BeatmapStarRating, Error> {
-    let folder = folder(p, state)?;
-    let filename = filename(p, state)?;
-    let songs_path = path_folder(p, state)?;
-    let path = songs_path.join(folder).join(filename);
-    let b = rosu_pp::Beatmap::from_path(path)?;
-    let diff_attrs = rosu_pp::Difficulty::new().calculate(&b);
-    let diff_dt = rosu_pp::Difficulty::new().mods(64).calculate(&b);
-    let diff_ht = rosu_pp::Difficulty::new().mods(256).calculate(&b);
-    let no_mod = diff_attrs.stars();
-    let dt = diff_dt.stars();
-    let ht = diff_ht.stars();
-    Ok(BeatmapStarRating { no_mod, dt, ht })
+/// Star rating of the current beatmap as played in the ruleset selected in
+/// the menu. `selected` falls back to no mod when the menu mods can't be
+/// read, and the beatmap stays in its own mode when the ruleset can't be
+pub fn star_rating<P: MemorySource>(p: &P, state: &mut State) -> Result<BeatmapStarRating, Error> {
+    let selected = menu_mods(p, state).unwrap_or_else(|e| {
+        tracing::debug!(error = %e, "no menu mods, selected star rating is no mod");
+        GameModsLegacy::NoMod
+    });
+    let ruleset = menu_ruleset(p, state)
+        .inspect_err(|e| tracing::debug!(error = %e, "no menu ruleset, beatmap kept in its mode"))
+        .ok();
+    let in_ruleset = |mods: GameModsLegacy| {
+        let mods = DifficultyMods::new(mods);
+        match ruleset {
+            Some(mode) => mods.mode(mode),
+            None => mods,
+        }
+    };
+    let stars = difficulty::attributes_many(
+        &md5(p, state)?,
+        path(p, state)?,
+        &[
+            in_ruleset(GameModsLegacy::NoMod),
+            in_ruleset(GameModsLegacy::DoubleTime),
+            in_ruleset(GameModsLegacy::HalfTime),
+            in_ruleset(selected),
+        ],
+    )?;
+    Ok(BeatmapStarRating {
+        no_mod: stars[0].stars(),
+        dt: stars[1].stars(),
+        ht: stars[2].stars(),
+        selected: stars[3].stars(),
+    })
+}
+
+/// Difficulty attributes of the current beatmap for `mods`, see [`difficulty`]
+pub fn difficulty_attributes<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+    mods: impl Into<DifficultyMods>,
+) -> Result<DifficultyAttributes, Error> {
+    difficulty::attributes(&md5(p, state)?, path(p, state)?, mods)
+}
+
+pub fn star_rating_with_mods<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+    mods: impl Into<DifficultyMods>,
+) -> Result<f64, Error> {
+    Ok(difficulty_attributes(p, state, mods)?.stars())
+}
+
+/// Stats of the current beatmap in the ruleset and with the mods selected in
+/// the menu
+pub fn modded_stats<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+) -> Result<BeatmapModdedStats, Error> {
+    let mods = menu_mods(p, state)?;
+    modded_stats_with_mods(p, state, mods)
+}
+
+/// Stats of the current beatmap in the selected ruleset with `mods`, e.g. the
+/// gameplay mods
+pub fn modded_stats_with_mods<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+    mods: impl Into<DifficultyMods>,
+) -> Result<BeatmapModdedStats, Error> {
+    let mode = menu_ruleset(p, state)?;
+    difficulty::modded_stats(path(p, state)?, mode, mods)
 }
 
-pub fn md5(p: &Process, state: &mut State) -> Result<String, Error> {
+pub fn md5<P: MemorySource>(p: &P, state: &mut State) -> Result<String, Error> {
     // TODO: implement this for now will get from memory
     crate::reader::beatmap::stable::memory::md5(p, state)
 }
-pub fn stats(p: &Process, state: &mut State) -> Result<BeatmapStats, Error> {
+pub fn stats<P: MemorySource>(p: &P, state: &mut State) -> Result<BeatmapStats, Error> {
     let beatmap_addr = path(p, state)?;
     let b = RmBeatmap::from_path(beatmap_addr)?;
     Ok(BeatmapStats {
@@ -154,7 +217,8 @@ pub fn stats(p: &Process, state: &mut State) -> Result<BeatmapStats, Error> {
     })
 }
 
-pub fn info(p: &Process, state: &mut State) -> Result<BeatmapInfo, Error> {
+pub fn info<P: MemorySource>(p: &P, state: &mut State) -> Result<BeatmapInfo, Error> {
+    let offsets = offsets();
     let beatmap_file = path(p, state)?;
     let beatmap_addr = beatmap_addr(p, state)?;
     let b = RmBeatmap::from_path(beatmap_file)?;
@@ -190,10 +254,10 @@ pub fn info(p: &Process, state: &mut State) -> Result<BeatmapInfo, Error> {
                 .count() as i32,
         },
         location: BeatmapLocation {
-            folder: p.read_string(beatmap_addr + BEATMAP_OFFSET.location.folder)?,
-            filename: p.read_string(beatmap_addr + BEATMAP_OFFSET.location.filename)?,
-            audio: p.read_string(beatmap_addr + BEATMAP_OFFSET.location.audio)?,
-            cover: p.read_string(beatmap_addr + BEATMAP_OFFSET.location.cover)?,
+            folder: p.read_string(beatmap_addr + offsets.beatmap.location.folder)?,
+            filename: p.read_string(beatmap_addr + offsets.beatmap.location.filename)?,
+            audio: p.read_string(beatmap_addr + offsets.beatmap.location.audio)?,
+            cover: p.read_string(beatmap_addr + offsets.beatmap.location.cover)?,
         },
     })
 }
@@ -1,99 +1,106 @@
 use crate::common::GameMode;
 use crate::generate_offset_getter;
+use crate::memory::MemorySource;
 use crate::reader::beatmap::common::{
     BeatmapInfo, BeatmapLocation, BeatmapMetadata, BeatmapStats, BeatmapStatus,
     BeatmapTechnicalInfo,
 };
-use crate::reader::beatmap::stable::{beatmap_addr, offset::BEATMAP_OFFSET};
+use crate::reader::beatmap::stable::beatmap_addr;
 use crate::reader::helpers::{read_f32, read_i32, read_string, read_u32};
+use crate::reader::offsets::offsets;
 use crate::reader::structs::State;
 use crate::Error;
-use rosu_mem::process::{Process, ProcessTraits};
 
 generate_offset_getter! {
-    id: i32 = read_i32(BEATMAP_OFFSET.technical.id, beatmap_addr);
-    set_id: i32 = read_i32(BEATMAP_OFFSET.technical.set_id, beatmap_addr);
-    tags: String = read_string(BEATMAP_OFFSET.metadata.tags, beatmap_addr);
-    length: i32 = read_i32(BEATMAP_OFFSET.stats.total_length, beatmap_addr);
-    drain_time: i32 = read_i32(BEATMAP_OFFSET.stats.drain_time, beatmap_addr);
-    author: String = read_string(BEATMAP_OFFSET.metadata.author, beatmap_addr);
-    creator: String = read_string(BEATMAP_OFFSET.metadata.creator, beatmap_addr);
-    md5: String = read_string(BEATMAP_OFFSET.technical.md5, beatmap_addr);
-    title_romanized: String = read_string(BEATMAP_OFFSET.metadata.title_romanized, beatmap_addr);
-    title: String = read_string(BEATMAP_OFFSET.metadata.title_original, beatmap_addr);
-    difficulty: String = read_string(BEATMAP_OFFSET.metadata.difficulty, beatmap_addr);
-    od: f32 = read_f32(BEATMAP_OFFSET.stats.od, beatmap_addr);
-    ar: f32 = read_f32(BEATMAP_OFFSET.stats.ar, beatmap_addr);
-    cs: f32 = read_f32(BEATMAP_OFFSET.stats.cs, beatmap_addr);
-    hp: f32 = read_f32(BEATMAP_OFFSET.stats.hp, beatmap_addr);
-    object_count: u32 = read_u32(BEATMAP_OFFSET.stats.object_count, beatmap_addr);
-    slider_count: i32 = read_i32(BEATMAP_OFFSET.stats.slider_count, beatmap_addr);
-    folder: String = read_string(BEATMAP_OFFSET.location.folder, beatmap_addr);
-    filename: String = read_string(BEATMAP_OFFSET.location.filename, beatmap_addr);
-    audio: String = read_string(BEATMAP_OFFSET.location.audio, beatmap_addr);
-    cover: String = read_string(BEATMAP_OFFSET.location.cover, beatmap_addr);
-    mode: GameMode = read_i32(BEATMAP_OFFSET.technical.mode, beatmap_addr);
-    status: BeatmapStatus = read_i32(BEATMAP_OFFSET.technical.ranked_status, beatmap_addr);
+    id: i32 = read_i32(offsets().beatmap.technical.id, beatmap_addr);
+    set_id: i32 = read_i32(offsets().beatmap.technical.set_id, beatmap_addr);
+    tags: String = read_string(offsets().beatmap.metadata.tags, beatmap_addr);
+    length: i32 = read_i32(offsets().beatmap.stats.total_length, beatmap_addr);
+    drain_time: i32 = read_i32(offsets().beatmap.stats.drain_time, beatmap_addr);
+    author: String = read_string(offsets().beatmap.metadata.author, beatmap_addr);
+    creator: String = read_string(offsets().beatmap.metadata.creator, beatmap_addr);
+    md5: String = read_string(offsets().beatmap.technical.md5, beatmap_addr);
+    title_romanized: String = read_string(offsets().beatmap.metadata.title_romanized, beatmap_addr);
+    title: String = read_string(offsets().beatmap.metadata.title_original, beatmap_addr);
+    difficulty: String = read_string(offsets().beatmap.metadata.difficulty, beatmap_addr);
+    od: f32 = read_f32(offsets().beatmap.stats.od, beatmap_addr);
+    ar: f32 = read_f32(offsets().beatmap.stats.ar, beatmap_addr);
+    cs: f32 = read_f32(offsets().beatmap.stats.cs, beatmap_addr);
+    hp: f32 = read_f32(offsets().beatmap.stats.hp, beatmap_addr);
+    object_count: u32 = read_u32(offsets().beatmap.stats.object_count, beatmap_addr);
+    slider_count: i32 = read_i32(offsets().beatmap.stats.slider_count, beatmap_addr);
+    folder: String = read_string(offsets().beatmap.location.folder, beatmap_addr);
+    filename: String = read_string(offsets().beatmap.location.filename, beatmap_addr);
+    audio: String = read_string(offsets().beatmap.location.audio, beatmap_addr);
+    cover: String = read_string(offsets().beatmap.location.cover, beatmap_addr);
+    mode: GameMode = read_i32(offsets().beatmap.technical.mode, beatmap_addr);
+    status: BeatmapStatus = read_i32(offsets().beatmap.technical.ranked_status, beatmap_addr);
 }
 
-pub fn stats(p: &Process, state: &mut State) -> Result<BeatmapStats, Error> {
+pub fn stats<P: MemorySource>(p: &P, state: &mut State) -> Result<BeatmapStats, Error> {
     let beatmap_addr = beatmap_addr(p, state)?;
-    let mut buffer = [0u8; size_of::<f32>() * 4];
-    p.read(beatmap_addr + 0x2c, size_of::<f32>() * 4, &mut buffer)?;
+    stats_at(p, state, beatmap_addr)
+}
 
+/// Stats of the beatmap at `beatmap_addr`
+fn stats_at<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+    beatmap_addr: i32,
+) -> Result<BeatmapStats, Error> {
+    let stats = &offsets().beatmap.stats;
     Ok(BeatmapStats {
-        ar: f32::from_le_bytes(buffer[0..4].try_into().unwrap()),
-        cs: f32::from_le_bytes(buffer[4..8].try_into().unwrap()),
-        hp: f32::from_le_bytes(buffer[8..12].try_into().unwrap()),
-        od: f32::from_le_bytes(buffer[12..].try_into().unwrap()),
-        length: p.read_i32(beatmap_addr + BEATMAP_OFFSET.stats.total_length)?,
+        ar: p.read_f32(beatmap_addr + stats.ar)?,
+        cs: p.read_f32(beatmap_addr + stats.cs)?,
+        hp: p.read_f32(beatmap_addr + stats.hp)?,
+        od: p.read_f32(beatmap_addr + stats.od)?,
+        length: p.read_i32(beatmap_addr + stats.total_length)?,
         star_rating: crate::reader::beatmap::stable::file::star_rating(p, state)?,
-        object_count: p.read_i32(beatmap_addr + BEATMAP_OFFSET.stats.object_count)?,
-        slider_count: p.read_i32(beatmap_addr + BEATMAP_OFFSET.stats.slider_count)?,
+        object_count: p.read_i32(beatmap_addr + stats.object_count)?,
+        slider_count: p.read_i32(beatmap_addr + stats.slider_count)?,
     })
 }
 
-pub fn info(p: &Process, state: &mut State) -> Result<BeatmapInfo, Error> {
+pub fn info<P: MemorySource>(p: &P, state: &mut State) -> Result<BeatmapInfo, Error> {
     let beatmap_addr = beatmap_addr(p, state)?;
+    info_at(p, state, beatmap_addr)
+}
 
-    let mut buffer = [0u8; size_of::<f32>() * 4];
-    p.read(beatmap_addr + 0x2c, size_of::<f32>() * 4, &mut buffer)?;
+/// Beatmap info of the beatmap at `beatmap_addr`
+pub(crate) fn info_at<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+    beatmap_addr: i32,
+) -> Result<BeatmapInfo, Error> {
+    let offsets = offsets();
 
-    let stats = BeatmapStats {
-        ar: f32::from_le_bytes(buffer[0..4].try_into().unwrap()),
-        cs: f32::from_le_bytes(buffer[4..8].try_into().unwrap()),
-        hp: f32::from_le_bytes(buffer[8..12].try_into().unwrap()),
-        od: f32::from_le_bytes(buffer[12..].try_into().unwrap()),
-        length: p.read_i32(beatmap_addr + BEATMAP_OFFSET.stats.total_length)?,
-        star_rating: crate::reader::beatmap::stable::file::star_rating(p, state)?,
-        object_count: p.read_i32(beatmap_addr + BEATMAP_OFFSET.stats.object_count)?,
-        slider_count: p.read_i32(beatmap_addr + BEATMAP_OFFSET.stats.slider_count)?,
-    };
+    let stats = stats_at(p, state, beatmap_addr)?;
     Ok(BeatmapInfo {
         technical: BeatmapTechnicalInfo {
-            md5: p.read_string(beatmap_addr + BEATMAP_OFFSET.technical.md5)?,
-            id: p.read_i32(beatmap_addr + BEATMAP_OFFSET.technical.id)?,
-            set_id: p.read_i32(beatmap_addr + BEATMAP_OFFSET.technical.set_id)?,
-            mode: GameMode::Osu,
+            md5: p.read_string(beatmap_addr + offsets.beatmap.technical.md5)?,
+            id: p.read_i32(beatmap_addr + offsets.beatmap.technical.id)?,
+            set_id: p.read_i32(beatmap_addr + offsets.beatmap.technical.set_id)?,
+            mode: GameMode::from(p.read_i32(beatmap_addr + offsets.beatmap.technical.mode)?),
             ranked_status: BeatmapStatus::from(
-                p.read_i32(beatmap_addr + BEATMAP_OFFSET.technical.ranked_status)?,
+                p.read_i32(beatmap_addr + offsets.beatmap.technical.ranked_status)?,
             ),
         },
         metadata: BeatmapMetadata {
-            author: p.read_string(beatmap_addr + BEATMAP_OFFSET.metadata.author)?,
-            creator: p.read_string(beatmap_addr + BEATMAP_OFFSET.metadata.creator)?,
+            author: p.read_string(beatmap_addr + offsets.beatmap.metadata.author)?,
+            creator: p.read_string(beatmap_addr + offsets.beatmap.metadata.creator)?,
             title_romanized: p
-                .read_string(beatmap_addr + BEATMAP_OFFSET.metadata.title_romanized)?,
-            title_original: p.read_string(beatmap_addr + BEATMAP_OFFSET.metadata.title_original)?,
-            difficulty: p.read_string(beatmap_addr + BEATMAP_OFFSET.metadata.difficulty)?,
-            tags: p.read_string(beatmap_addr + BEATMAP_OFFSET.metadata.tags)?,
+                .read_string(beatmap_addr + offsets.beatmap.metadata.title_romanized)?,
+            title_original: p
+                .read_string(beatmap_addr + offsets.beatmap.metadata.title_original)?,
+            difficulty: p.read_string(beatmap_addr + offsets.beatmap.metadata.difficulty)?,
+            tags: p.read_string(beatmap_addr + offsets.beatmap.metadata.tags)?,
         },
         stats,
         location: BeatmapLocation {
-            folder: p.read_string(beatmap_addr + BEATMAP_OFFSET.location.folder)?,
-            filename: p.read_string(beatmap_addr + BEATMAP_OFFSET.location.filename)?,
-            audio: p.read_string(beatmap_addr + BEATMAP_OFFSET.location.audio)?,
-            cover: p.read_string(beatmap_addr + BEATMAP_OFFSET.location.cover)?,
+            folder: p.read_string(beatmap_addr + offsets.beatmap.location.folder)?,
+            filename: p.read_string(beatmap_addr + offsets.beatmap.location.filename)?,
+            audio: p.read_string(beatmap_addr + offsets.beatmap.location.audio)?,
+            cover: p.read_string(beatmap_addr + offsets.beatmap.location.cover)?,
         },
     })
 }
@@ -2,20 +2,27 @@ pub mod file;
 pub mod memory;
 pub mod offset;
 
-use crate::reader::beatmap::stable::offset::*;
-use crate::reader::common::stable::memory::check_game_state;
+use crate::memory::MemorySource;
+use crate::reader::common::stable::memory::game_state;
 use crate::reader::common::GameState;
-use crate::reader::structs::State;
+use crate::reader::offsets::offsets;
+use crate::reader::structs::{SignatureName, State};
 use crate::Error;
-use rosu_mem::process::{Process, ProcessTraits};
 
-pub(crate) fn beatmap_addr(p: &Process, state: &mut State) -> Result<i32, Error> {
-    match check_game_state(p, state, GameState::SongSelect)?
-        || check_game_state(p, state, GameState::Editor)?
-        || check_game_state(p, state, GameState::Playing)?
-        || check_game_state(p, state, GameState::ResultScreen)?
-    {
-        true => Ok(p.read_i32(p.read_i32(state.addresses.base - BEATMAP_OFFSET.ptr)?)?),
-        false => Err(Error::NotAvailable("Not in song select".to_string())),
+pub(crate) fn beatmap_addr<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
+    match game_state(p, state)? {
+        GameState::SongSelect
+        | GameState::Editor
+        | GameState::Playing
+        | GameState::ResultScreen => beatmap_addr_unchecked(p, state),
+        _ => Err(Error::NotAvailable("Not in song select".to_string())),
     }
 }
+
+/// Same as `beatmap_addr` for callers that already checked the game state
+pub(crate) fn beatmap_addr_unchecked<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+) -> Result<i32, Error> {
+    p.read_i32(p.read_i32(state.address(p, SignatureName::Base)? - offsets().beatmap.ptr)?)
+}
@@ -4,14 +4,14 @@ use crate::reader::beatmap::common::BeatmapOffset;
 use crate::reader::beatmap::common::BeatmapStatsOffset;
 use crate::reader::beatmap::common::BeatmapTechnicalOffset;
 
-pub(crate) const BEATMAP_LOCATION_OFFSET: BeatmapLocationOffset = BeatmapLocationOffset {
+pub const BEATMAP_LOCATION_OFFSET: BeatmapLocationOffset = BeatmapLocationOffset {
     folder: 0x78,
     filename: 0x90,
     audio: 0x64,
     cover: 0x68,
 };
 
-pub(crate) const BEATMAP_STATS_OFFSET: BeatmapStatsOffset = BeatmapStatsOffset {
+pub const BEATMAP_STATS_OFFSET: BeatmapStatsOffset = BeatmapStatsOffset {
     ar: 0x2c,
     cs: 0x30,
     hp: 0x34,
@@ -23,7 +23,7 @@ pub(crate) const BEATMAP_STATS_OFFSET: BeatmapStatsOffset = BeatmapStatsOffset {
     slider_count: 0x146,
 };
 
-pub(crate) const BEATMAP_TECHNICAL_OFFSET: BeatmapTechnicalOffset = BeatmapTechnicalOffset {
+pub const BEATMAP_TECHNICAL_OFFSET: BeatmapTechnicalOffset = BeatmapTechnicalOffset {
     md5: 0x6c,
     id: 0xc8,
     set_id: 0xcc,
@@ -31,7 +31,7 @@ pub(crate) const BEATMAP_TECHNICAL_OFFSET: BeatmapTechnicalOffset = BeatmapTechn
     ranked_status: 0x12c,
 };
 
-pub(crate) const BEATMAP_OFFSET: BeatmapOffset = BeatmapOffset {
+pub const BEATMAP_OFFSET: BeatmapOffset = BeatmapOffset {
     ptr: 0xC,
     metadata: BEATMAP_METADATA_OFFSET,
     location: BEATMAP_LOCATION_OFFSET,
@@ -39,7 +39,7 @@ pub(crate) const BEATMAP_OFFSET: BeatmapOffset = BeatmapOffset {
     technical: BEATMAP_TECHNICAL_OFFSET,
 };
 
-pub(crate) const BEATMAP_METADATA_OFFSET: BeatmapMetadataOffset = BeatmapMetadataOffset {
+pub const BEATMAP_METADATA_OFFSET: BeatmapMetadataOffset = BeatmapMetadataOffset {
     author: 0x18,
     creator: 0x7C,
     title_romanized: 0x24,
@@ -49,3 +49,33 @@ pub(crate) const BEATMAP_METADATA_OFFSET: BeatmapMetadataOffset = BeatmapMetadat
 };
 
 // 0x12a Could be draintime ->
+
+impl Default for BeatmapOffset {
+    fn default() -> Self {
+        BEATMAP_OFFSET
+    }
+}
+
+impl Default for BeatmapStatsOffset {
+    fn default() -> Self {
+        BEATMAP_STATS_OFFSET
+    }
+}
+
+impl Default for BeatmapLocationOffset {
+    fn default() -> Self {
+        BEATMAP_LOCATION_OFFSET
+    }
+}
+
+impl Default for BeatmapTechnicalOffset {
+    fn default() -> Self {
+        BEATMAP_TECHNICAL_OFFSET
+    }
+}
+
+impl Default for BeatmapMetadataOffset {
+    fn default() -> Self {
+        BEATMAP_METADATA_OFFSET
+    }
+}
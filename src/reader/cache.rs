@@ -0,0 +1,184 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Instant, UNIX_EPOCH};
+
+use crate::memory::MemorySource;
+use crate::reader::common::stable::file::version_from_dir;
+use crate::reader::common::OsuVersion;
+use crate::reader::offsets::offsets;
+use crate::reader::structs::{SignatureName, SignatureStatus, StaticAddresses};
+use crate::Error;
+use rosu_mem::signature::{find_signature, Signature};
+
+const HEADER: &str = "rosu-memory-lib signature cache v2";
+
+/// Identifies a running osu! build without reading its executable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutableKey {
+    /// Address `osu!.exe` is loaded at, 0 when the source can't tell
+    pub module_base: i32,
+    pub version: Option<OsuVersion>,
+    /// Size of `osu!.exe`
+    pub size: u64,
+    /// Last modification of `osu!.exe`, in seconds since the unix epoch
+    pub modified: u64,
+}
+
+impl ExecutableKey {
+    /// Key of the osu! behind `p`, from its module base, build and the
+    /// metadata of `osu!.exe`
+    pub fn new<P: MemorySource>(p: &P) -> Result<Self, Error> {
+        let dir = p
+            .executable_dir()
+            .ok_or_else(|| Error::NotAvailable("Executable directory is unknown".to_string()))?;
+        let metadata = fs::metadata(dir.join("osu!.exe"))?;
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Ok(Self {
+            module_base: p.module_base().unwrap_or(0),
+            version: version_from_dir(&dir).ok(),
+            size: metadata.len(),
+            modified,
+        })
+    }
+}
+
+/// Signature addresses saved on disk between attaches.
+///
+/// Entries are keyed by where `osu!.exe` is loaded and by its build, size and
+/// modification time so an update invalidates them, and every cached address
+/// is checked against its signature before being trusted, which costs one
+/// small read per signature instead of a scan of the whole process. Anything
+/// stale falls back to a full scan.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rosu_memory_lib::reader::cache::SignatureCache;
+/// use rosu_memory_lib::reader::try_attach_cached;
+///
+/// let cache = SignatureCache::new(std::env::temp_dir().join("osu-signatures.cache"));
+/// let (state, process) = try_attach_cached(&cache)?;
+/// # Ok::<(), rosu_memory_lib::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SignatureCache {
+    path: PathBuf,
+}
+
+impl SignatureCache {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Cached addresses for the process, `None` if the cache is missing, was
+    /// written for another build or any address no longer matches its signature
+    pub fn load<P: MemorySource>(&self, p: &P) -> Option<StaticAddresses> {
+        let key = ExecutableKey::new(p).ok()?;
+        let content = fs::read_to_string(&self.path).ok()?;
+        let (cached_key, addresses) = parse(&content)?;
+        if cached_key != key {
+            tracing::debug!(path = %self.path.display(), "signature cache is for another build");
+            return None;
+        }
+
+        for (name, sig) in offsets().signatures.entries() {
+            if let SignatureStatus::Resolved(addr) = addresses.status_of(name) {
+                if !matches_at(p, addr, sig) {
+                    tracing::debug!(name = name.name(), "cached signature address is stale");
+                    return None;
+                }
+            }
+        }
+        Some(addresses)
+    }
+
+    /// Writes the resolved addresses of `addresses`, pending and missing
+    /// signatures are left out
+    pub fn save<P: MemorySource>(&self, p: &P, addresses: &StaticAddresses) -> Result<(), Error> {
+        let key = ExecutableKey::new(p)?;
+        let version = key
+            .version
+            .map_or_else(|| "-".to_string(), |version| version.to_string());
+
+        let mut content = format!(
+            "{HEADER}\nexecutable {:#x} {version} {} {}\n",
+            key.module_base as u32, key.size, key.modified
+        );
+        for (name, status) in addresses.report() {
+            if let SignatureStatus::Resolved(addr) = status {
+                content.push_str(&format!("{name} {:#x}\n", addr as u32));
+            }
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+
+    /// Cached addresses if they are still valid, otherwise a full scan
+    /// whose result is written back to the cache
+    pub fn load_or_scan<P: MemorySource>(&self, p: &P) -> Result<StaticAddresses, Error> {
+        let start = Instant::now();
+        if let Some(addresses) = self.load(p) {
+            tracing::info!(elapsed = ?start.elapsed(), "signatures loaded from cache");
+            return Ok(addresses);
+        }
+
+        let addresses = StaticAddresses::new(p)?;
+        if let Err(e) = self.save(p, &addresses) {
+            tracing::warn!(error = %e, "couldn't write signature cache");
+        }
+        Ok(addresses)
+    }
+}
+
+fn parse(content: &str) -> Option<(ExecutableKey, StaticAddresses)> {
+    let mut lines = content.lines();
+    if lines.next()? != HEADER {
+        return None;
+    }
+
+    let mut executable = lines.next()?.split(' ');
+    if executable.next()? != "executable" {
+        return None;
+    }
+    let key = ExecutableKey {
+        module_base: u32::from_str_radix(executable.next()?.trim_start_matches("0x"), 16).ok()?
+            as i32,
+        version: match executable.next()? {
+            "-" => None,
+            version => Some(version.parse().ok()?),
+        },
+        size: executable.next()?.parse().ok()?,
+        modified: executable.next()?.parse().ok()?,
+    };
+
+    let mut addresses = StaticAddresses::default();
+    for line in lines {
+        let (name, addr) = line.split_once(' ')?;
+        let name = SignatureName::from_name(name)?;
+        let addr = u32::from_str_radix(addr.trim_start_matches("0x"), 16).ok()?;
+        addresses.set(name, addr as i32);
+    }
+    Some((key, addresses))
+}
+
+/// Whether `sig` still matches the bytes at `addr`
+fn matches_at<P: MemorySource>(p: &P, addr: i32, sig: &str) -> bool {
+    let Ok(signature) = Signature::from_str(sig) else {
+        return false;
+    };
+    let len = sig.split(' ').count();
+    let mut buff = vec![0u8; len];
+    p.read(addr, len, &mut buff).is_ok() && find_signature(&buff, &signature) == Some(0)
+}
@@ -1,10 +1,15 @@
 pub mod stable;
+use std::cmp::Ordering;
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use crate::impl_osu_accessor;
-use crate::reader::structs::State;
+use crate::memory::MemorySource;
+use crate::reader::structs::{State, StateHandle};
 use crate::Error;
 use rosu_mem::process::Process;
+use rosu_mods::GameModsLegacy;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub enum OsuClientKind {
@@ -13,8 +18,15 @@ pub enum OsuClientKind {
     Lazer,
 }
 
+/// Serialized as its `to_string()` value
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum GameMode {
+    #[cfg_attr(feature = "serde", serde(rename = "std"))]
     Osu,
     Taiko,
     Catch,
@@ -54,7 +66,9 @@ impl GameMode {
     }
 }
 
+/// Serialized as the variant name (`"SongSelect"`)
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u32)]
 pub enum GameState {
     MainMenu = 0,
@@ -106,14 +120,92 @@ impl From<u32> for GameState {
     }
 }
 
-pub struct CommonReader<'a> {
-    pub process: &'a Process,
-    pub state: &'a mut State,
+/// osu! stable build, as in `b20250108.3cuttingedge`.
+///
+/// Builds compare by date then revision, the release stream only breaks ties.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct OsuVersion {
+    /// Build date as `yyyymmdd`
+    pub date: u32,
+    pub revision: u32,
+    /// Release stream suffix (`cuttingedge`, `beta`...), empty on stable
+    pub stream: String,
+}
+
+impl FromStr for OsuVersion {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::Parse(format!("Invalid osu! version {value}"));
+
+        let value = value.trim();
+        let value = value.strip_prefix('b').unwrap_or(value);
+        let (date, rest) = value.split_once('.').unwrap_or((value, "0"));
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (revision, stream) = rest.split_at(digits);
+
+        if date.len() != 8 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            date: date.parse().map_err(|_| invalid())?,
+            revision: if revision.is_empty() {
+                0
+            } else {
+                revision.parse().map_err(|_| invalid())?
+            },
+            stream: stream.to_string(),
+        })
+    }
+}
+
+impl TryFrom<String> for OsuVersion {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<OsuVersion> for String {
+    fn from(value: OsuVersion) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for OsuVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "b{}.{}{}", self.date, self.revision, self.stream)
+    }
+}
+
+impl PartialOrd for OsuVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OsuVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.date, self.revision, &self.stream).cmp(&(other.date, other.revision, &other.stream))
+    }
+}
+
+pub struct CommonReader<'a, P: MemorySource = Process, S: StateHandle = &'a mut State> {
+    pub process: &'a P,
+    pub state: S,
     pub osu_type: OsuClientKind,
 }
 
-impl<'a> CommonReader<'a> {
-    pub fn new(p: &'a Process, state: &'a mut State, osu_type: OsuClientKind) -> Self {
+impl<'a, P: MemorySource, S: StateHandle> CommonReader<'a, P, S> {
+    pub fn new(p: &'a P, state: S, osu_type: OsuClientKind) -> Self {
         Self {
             process: p,
             state,
@@ -123,14 +215,23 @@ impl<'a> CommonReader<'a> {
 
     impl_osu_accessor! {
         fn game_state() -> GameState => stable::memory::game_state,
-        fn menu_game_mode() -> u32 => stable::memory::menu_game_mode,
+        fn menu_mods() -> GameModsLegacy => stable::memory::menu_mods,
+        fn menu_ruleset() -> GameMode => stable::memory::menu_ruleset,
         fn path_folder() -> PathBuf => stable::memory::path_folder,
+        fn osu_version() -> OsuVersion => stable::file::osu_version,
+    }
+
+    #[deprecated(note = "reads the menu mods, use `menu_mods` (or `menu_ruleset` for the mode)")]
+    pub fn menu_game_mode(&mut self) -> Result<GameModsLegacy, Error> {
+        self.menu_mods()
     }
 
     pub fn check_game_state(&mut self, g_state: GameState) -> Result<bool, Error> {
         match self.osu_type {
             OsuClientKind::Stable => {
-                stable::memory::check_game_state(self.process, self.state, g_state)
+                let process = self.process;
+                self.state
+                    .with_state(|state| stable::memory::check_game_state(process, state, g_state))
             }
             _ => Err(Error::Unsupported(
                 "Unsupported osu type for now".to_string(),
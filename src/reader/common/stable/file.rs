@@ -0,0 +1,196 @@
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::memory::MemorySource;
+use crate::reader::common::OsuVersion;
+use crate::reader::structs::State;
+use crate::Error;
+
+/// `RT_VERSION` resource type
+const RT_VERSION: u32 = 16;
+/// High bit of a resource entry offset, set when it points to a subdirectory
+const RESOURCE_SUBDIRECTORY: u32 = 0x8000_0000;
+/// `VS_FIXEDFILEINFO::dwSignature`
+const FIXED_FILE_INFO_SIGNATURE: u32 = 0xFEEF_04BD;
+
+/// Build of the running osu!, read from the version resource of `osu!.exe`.
+///
+/// Falls back to the `LastVersion` osu! writes to its per-user config on
+/// startup (`osu!.<username>.cfg` next to the executable) when the executable
+/// has no usable version, that value can be stale right after an update or
+/// belong to another user sharing the install.
+pub fn osu_version<P: MemorySource>(p: &P, _state: &mut State) -> Result<OsuVersion, Error> {
+    let dir = p
+        .executable_dir()
+        .ok_or_else(|| Error::NotAvailable("Executable directory is unknown".to_string()))?;
+    version_from_dir(&dir)
+}
+
+pub(crate) fn version_from_dir(dir: &Path) -> Result<OsuVersion, Error> {
+    match version_from_executable(&dir.join("osu!.exe")) {
+        Ok(version) => Ok(version),
+        Err(e) => {
+            tracing::debug!(error = %e, "no executable version, using the user config");
+            version_from_config(dir)
+        }
+    }
+}
+
+/// Version resource of a PE executable, only the headers and the resource
+/// section are read
+fn version_from_executable(path: &Path) -> Result<OsuVersion, Error> {
+    let invalid = |what: &str| Error::Parse(format!("{}: {what}", path.display()));
+    let mut file = File::open(path)?;
+
+    let dos = read_at(&mut file, 0, 0x40)?;
+    if !dos.starts_with(b"MZ") {
+        return Err(invalid("not an executable"));
+    }
+    let pe = u32_at(&dos, 0x3C)? as u64;
+    let coff = read_at(&mut file, pe, 24)?;
+    if !coff.starts_with(b"PE\0\0") {
+        return Err(invalid("not a PE executable"));
+    }
+    let section_count = u16_at(&coff, 6)? as usize;
+    let optional_header_size = u16_at(&coff, 20)? as u64;
+    let sections = read_at(
+        &mut file,
+        pe + 24 + optional_header_size,
+        section_count * 40,
+    )?;
+    let rsrc = sections
+        .chunks_exact(40)
+        .find(|section| section.starts_with(b".rsrc\0"))
+        .ok_or_else(|| invalid("no resource section"))?;
+    let rsrc_rva = u32_at(rsrc, 12)?;
+    let resources = read_at(
+        &mut file,
+        u32_at(rsrc, 20)? as u64,
+        u32_at(rsrc, 16)? as usize,
+    )?;
+
+    // type -> name -> language -> data
+    let names = resource_entry(&resources, 0, Some(RT_VERSION))?
+        .checked_sub(RESOURCE_SUBDIRECTORY)
+        .ok_or_else(|| invalid("malformed version resource"))?;
+    let languages = resource_entry(&resources, names, None)?
+        .checked_sub(RESOURCE_SUBDIRECTORY)
+        .ok_or_else(|| invalid("malformed version resource"))?;
+    let data = resource_entry(&resources, languages, None)?;
+    let start = u32_at(&resources, data as usize)?
+        .checked_sub(rsrc_rva)
+        .ok_or_else(|| invalid("malformed version resource"))? as usize;
+    let size = u32_at(&resources, data as usize + 4)? as usize;
+    let info = resources
+        .get(start..start + size)
+        .ok_or_else(|| invalid("truncated version resource"))?;
+
+    parse_version_info(info).ok_or_else(|| invalid("no osu! build in the version resource"))
+}
+
+/// Offset stored in the first entry of the resource directory at `dir`
+/// matching `id`, any entry when `id` is `None`
+fn resource_entry(resources: &[u8], dir: u32, id: Option<u32>) -> Result<u32, Error> {
+    let dir = dir as usize;
+    let count = u16_at(resources, dir + 12)? as usize + u16_at(resources, dir + 14)? as usize;
+    for entry in (0..count).map(|i| dir + 16 + i * 8) {
+        let matches = match id {
+            Some(id) => u32_at(resources, entry)? == id,
+            None => true,
+        };
+        if matches {
+            return u32_at(resources, entry + 4);
+        }
+    }
+    Err(Error::NotAvailable("No version resource".to_string()))
+}
+
+/// `bYYYYMMDD.R` from the version strings, or the `YYYY.MDD.R` numeric version
+fn parse_version_info(info: &[u8]) -> Option<OsuVersion> {
+    let from_strings = ["ProductVersion", "FileVersion"]
+        .into_iter()
+        .filter_map(|key| string_value(info, key)?.parse().ok())
+        .next();
+    if from_strings.is_some() {
+        return from_strings;
+    }
+
+    let fixed = (0..info.len())
+        .step_by(4)
+        .find(|i| u32_at(info, *i).ok() == Some(FIXED_FILE_INFO_SIGNATURE))?;
+    let most_significant = u32_at(info, fixed + 8).ok()?;
+    let least_significant = u32_at(info, fixed + 12).ok()?;
+    let (year, month_day) = (most_significant >> 16, most_significant & 0xFFFF);
+    if !(2007..=2100).contains(&year) || !(101..=1231).contains(&month_day) {
+        return None;
+    }
+    Some(OsuVersion {
+        date: year * 10000 + month_day,
+        revision: least_significant >> 16,
+        stream: String::new(),
+    })
+}
+
+/// Value of a `StringFileInfo` entry, stored as UTF-16 right after its key
+/// aligned on 4 bytes
+fn string_value(info: &[u8], key: &str) -> Option<String> {
+    let key: Vec<u8> = key
+        .encode_utf16()
+        .chain([0])
+        .flat_map(u16::to_le_bytes)
+        .collect();
+    let start = info.windows(key.len()).position(|w| w == key)? + key.len();
+    let value: Vec<u16> = info
+        .get(start.next_multiple_of(4)..)?
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|c| *c != 0)
+        .collect();
+    Some(String::from_utf16_lossy(&value))
+}
+
+fn version_from_config(dir: &Path) -> Result<OsuVersion, Error> {
+    // several users can share an install, the latest written config belongs
+    // to the running instance
+    let config = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("osu!.") && name.ends_with(".cfg") && name != "osu!.cfg"
+        })
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+        .ok_or_else(|| Error::NotAvailable("No osu! user config found".to_string()))?;
+
+    fs::read_to_string(&config)?
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "LastVersion")
+        .ok_or_else(|| Error::NotAvailable(format!("No LastVersion in {}", config.display())))?
+        .1
+        .parse()
+}
+
+fn read_at(file: &mut File, offset: u64, len: usize) -> Result<Vec<u8>, Error> {
+    let mut buff = vec![0u8; len];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut buff)?;
+    Ok(buff)
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Result<u16, Error> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| Error::Parse("Truncated executable".to_string()))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, Error> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| Error::Parse("Truncated executable".to_string()))
+}
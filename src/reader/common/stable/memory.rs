@@ -1,15 +1,16 @@
 use std::path::PathBuf;
 
 use crate::generate_offset_getter;
-use crate::reader::common::stable::offset::COMMON_OFFSET;
-use crate::reader::common::GameState;
+use crate::memory::MemorySource;
+use crate::reader::common::{GameMode, GameState};
 use crate::reader::helpers::{read_i32, read_u32};
-use crate::reader::structs::State;
+use crate::reader::offsets::offsets;
+use crate::reader::structs::{SignatureName, State};
 use crate::Error;
-use rosu_mem::process::{Process, ProcessTraits};
+use rosu_mods::GameModsLegacy;
 
-pub fn status_addr(p: &Process, state: &mut State) -> Result<i32, Error> {
-    Ok(p.read_i32(state.addresses.status - COMMON_OFFSET.status)?)
+pub fn status_addr<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
+    p.read_i32(state.address(p, SignatureName::Status)? - offsets().common.status)
 }
 
 /// Returns a path to the `Songs` folder
@@ -17,15 +18,17 @@ pub fn status_addr(p: &Process, state: &mut State) -> Result<i32, Error> {
 /// **Platform-specific**
 /// - Windows: Will return full absolute path to the `Songs` folder
 /// - Linux: Might return relative path, carefully check by yourself
-pub(crate) fn path_folder(p: &Process, state: &mut State) -> Result<PathBuf, Error> {
-    let settings_ptr = p.read_i32(state.addresses.settings + COMMON_OFFSET.settings_ptr)?;
-    let settings_addr = p.read_i32(settings_ptr + COMMON_OFFSET.settings_addr)?;
-    let path = p.read_string(settings_addr + COMMON_OFFSET.path)?;
+pub(crate) fn path_folder<P: MemorySource>(p: &P, state: &mut State) -> Result<PathBuf, Error> {
+    let offsets = offsets();
+    let settings_ptr =
+        p.read_i32(state.address(p, SignatureName::Settings)? + offsets.common.settings_ptr)?;
+    let settings_addr = p.read_i32(settings_ptr + offsets.common.settings_addr)?;
+    let path = p.read_string(settings_addr + offsets.common.path)?;
 
     // Attempt to construct a absolute path from executable path
     if path == "Songs" {
-        if let Some(executable_dir) = &p.executable_dir {
-            let path = executable_dir.clone().join("Songs");
+        if let Some(executable_dir) = p.executable_dir() {
+            let path = executable_dir.join("Songs");
 
             return Ok(path);
         }
@@ -34,37 +37,58 @@ pub(crate) fn path_folder(p: &Process, state: &mut State) -> Result<PathBuf, Err
     Ok(PathBuf::from(path))
 }
 
-pub fn menu_mods_addr(p: &Process, state: &mut State) -> Result<i32, Error> {
-    Ok(p.read_i32(state.addresses.menu_mods + COMMON_OFFSET.mods_ptr)?)
+pub fn menu_mods_addr<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
+    p.read_i32(state.address(p, SignatureName::MenuMods)? + offsets().common.mods_ptr)
 }
 
-pub fn playtime_addr(p: &Process, state: &mut State) -> Result<i32, Error> {
-    Ok(p.read_i32(state.addresses.playtime + COMMON_OFFSET.ig_time)?)
+pub fn menu_ruleset_addr<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
+    p.read_i32(state.address(p, SignatureName::Base)? + offsets().common.ruleset_ptr)
 }
 
-pub fn is_watching_replay(p: &Process, state: &mut State) -> Result<bool, Error> {
-    Ok(
-        p.read_u8(p.read_i32(state.addresses.check_replay_addr + COMMON_OFFSET.check_replay)?)?
-            == 1,
-    )
+pub fn playtime_addr<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
+    p.read_i32(state.address(p, SignatureName::Playtime)? + offsets().common.ig_time)
+}
+
+pub fn is_watching_replay<P: MemorySource>(p: &P, state: &mut State) -> Result<bool, Error> {
+    Ok(p.read_u8(p.read_i32(
+        state.address(p, SignatureName::CheckReplayAddr)? + offsets().common.check_replay,
+    )?)? == 1)
 }
 
 // Found by LoPij ! thanks
-pub fn is_paused(p: &Process, state: &mut State) -> Result<bool, Error> {
-       let base_some = p.read_i32(state.addresses.base + COMMON_OFFSET.pause_ptr)?;
-    let pause = p.read_i8(
-        base_some + COMMON_OFFSET.pause,
-    )?;
-    return Ok(pause == 1);
+pub fn is_paused<P: MemorySource>(p: &P, state: &mut State) -> Result<bool, Error> {
+    let base_some =
+        p.read_i32(state.address(p, SignatureName::Base)? + offsets().common.pause_ptr)?;
+    let pause = p.read_i8(base_some + offsets().common.pause)?;
+    Ok(pause == 1)
 }
 
 generate_offset_getter! {
     game_state: GameState = read_u32(0, status_addr);
-    menu_game_mode: u32 = read_u32(0, menu_mods_addr); // TODO: use GameModsLegacy
     game_time: i32 = read_i32(0, playtime_addr);
+    menu_ruleset: GameMode = read_i32(offsets().common.ruleset, menu_ruleset_addr);
+}
+
+/// Mods selected in the menu
+pub fn menu_mods<P: MemorySource>(p: &P, state: &mut State) -> Result<GameModsLegacy, Error> {
+    Ok(GameModsLegacy::from_bits(read_u32(
+        p,
+        state,
+        0,
+        menu_mods_addr,
+    )?))
+}
+
+#[deprecated(note = "reads the menu mods, use `menu_mods` (or `menu_ruleset` for the mode)")]
+pub fn menu_game_mode<P: MemorySource>(p: &P, state: &mut State) -> Result<GameModsLegacy, Error> {
+    menu_mods(p, state)
 }
 
 // this is an helper function to be faster for anyone
-pub fn check_game_state(p: &Process, state: &mut State, g_state: GameState) -> Result<bool, Error> {
+pub fn check_game_state<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+    g_state: GameState,
+) -> Result<bool, Error> {
     Ok(game_state(p, state)? == g_state)
 }
@@ -1,3 +1,9 @@
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct CommonOffset {
     pub settings_ptr: i32,
     pub settings_addr: i32,
@@ -8,9 +14,11 @@ pub struct CommonOffset {
     pub check_replay: i32,
     pub pause_ptr: i32,
     pub pause: i32,
+    pub ruleset_ptr: i32,
+    pub ruleset: i32,
 }
 
-pub(crate) const COMMON_OFFSET: CommonOffset = CommonOffset {
+pub const COMMON_OFFSET: CommonOffset = CommonOffset {
     settings_ptr: 0x8,
     settings_addr: 0xb8,
     path: 0x4,
@@ -20,4 +28,12 @@ pub(crate) const COMMON_OFFSET: CommonOffset = CommonOffset {
     check_replay: 0x46,
     pause_ptr: -0x33,
     pause: 0x21,
+    ruleset_ptr: -0x33,
+    ruleset: 0x0,
 };
+
+impl Default for CommonOffset {
+    fn default() -> Self {
+        COMMON_OFFSET
+    }
+}
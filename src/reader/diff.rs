@@ -0,0 +1,163 @@
+//! Field-level diffs between two JSON documents, in the JSON Patch
+//! ([RFC 6902](https://www.rfc-editor.org/rfc/rfc6902)) format.
+//!
+//! Only `add`, `remove` and `replace` are produced and understood. Objects
+//! are compared key by key and arrays index by index when their length
+//! didn't change, anything else that differs is replaced as a whole.
+//!
+//! # Examples
+//!
+//! ```
+//! use rosu_memory_lib::reader::diff::{apply, diff};
+//! use serde_json::json;
+//!
+//! let old = json!({ "combo": 10, "hits": { "300": 9, "miss": 0 } });
+//! let new = json!({ "combo": 11, "hits": { "300": 10, "miss": 0 } });
+//!
+//! let patch = diff(&old, &new);
+//! assert_eq!(patch.len(), 2);
+//!
+//! let mut applied = old.clone();
+//! apply(&mut applied, &patch)?;
+//! assert_eq!(applied, new);
+//! # Ok::<(), rosu_memory_lib::Error>(())
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::Error;
+
+/// One JSON Patch operation, `path` is a JSON Pointer like `/gameplay/combo`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+impl PatchOp {
+    pub fn path(&self) -> &str {
+        match self {
+            PatchOp::Add { path, .. }
+            | PatchOp::Remove { path }
+            | PatchOp::Replace { path, .. } => path,
+        }
+    }
+}
+
+/// Operations turning `old` into `new`, empty when they are equal
+pub fn diff(old: &Value, new: &Value) -> Vec<PatchOp> {
+    let mut patch = Vec::new();
+    diff_into(&mut patch, &mut String::new(), old, new);
+    patch
+}
+
+fn diff_into(patch: &mut Vec<PatchOp>, path: &mut String, old: &Value, new: &Value) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let len = push_token(path, key);
+                match new.get(key) {
+                    Some(new_value) => diff_into(patch, path, old_value, new_value),
+                    None => patch.push(PatchOp::Remove { path: path.clone() }),
+                }
+                path.truncate(len);
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    let len = push_token(path, key);
+                    patch.push(PatchOp::Add {
+                        path: path.clone(),
+                        value: new_value.clone(),
+                    });
+                    path.truncate(len);
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) if old.len() == new.len() => {
+            for (index, (old_value, new_value)) in old.iter().zip(new).enumerate() {
+                let len = push_token(path, &index.to_string());
+                diff_into(patch, path, old_value, new_value);
+                path.truncate(len);
+            }
+        }
+        (old, new) if old != new => patch.push(PatchOp::Replace {
+            path: path.clone(),
+            value: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Appends `/token` escaped as a JSON Pointer, returns the length to truncate back to
+fn push_token(path: &mut String, token: &str) -> usize {
+    let len = path.len();
+    path.push('/');
+    path.push_str(&token.replace('~', "~0").replace('/', "~1"));
+    len
+}
+
+/// Applies `patch` to `target` in order.
+///
+/// `Error::Parse` if an operation points somewhere that doesn't exist, in
+/// which case `target` may be partially patched.
+pub fn apply(target: &mut Value, patch: &[PatchOp]) -> Result<(), Error> {
+    for op in patch {
+        apply_one(target, op)?;
+    }
+    Ok(())
+}
+
+fn apply_one(target: &mut Value, op: &PatchOp) -> Result<(), Error> {
+    let invalid = || Error::Parse(format!("Cannot apply {op:?}"));
+    let path = op.path();
+    if path.is_empty() {
+        return match op {
+            PatchOp::Add { value, .. } | PatchOp::Replace { value, .. } => {
+                *target = value.clone();
+                Ok(())
+            }
+            PatchOp::Remove { .. } => Err(invalid()),
+        };
+    }
+
+    let (parent, last) = path.rsplit_once('/').ok_or_else(invalid)?;
+    let last = last.replace("~1", "/").replace("~0", "~");
+    let parent = target.pointer_mut(parent).ok_or_else(invalid)?;
+
+    match (parent, op) {
+        (Value::Object(map), PatchOp::Add { value, .. }) => {
+            map.insert(last, value.clone());
+        }
+        (Value::Object(map), PatchOp::Replace { value, .. }) => {
+            *map.get_mut(&last).ok_or_else(invalid)? = value.clone();
+        }
+        (Value::Object(map), PatchOp::Remove { .. }) => {
+            map.remove(&last).ok_or_else(invalid)?;
+        }
+        (Value::Array(array), PatchOp::Add { value, .. }) => {
+            let index = match last.as_str() {
+                "-" => array.len(),
+                index => index.parse().map_err(|_| invalid())?,
+            };
+            if index > array.len() {
+                return Err(invalid());
+            }
+            array.insert(index, value.clone());
+        }
+        (Value::Array(array), PatchOp::Replace { value, .. }) => {
+            let index: usize = last.parse().map_err(|_| invalid())?;
+            *array.get_mut(index).ok_or_else(invalid)? = value.clone();
+        }
+        (Value::Array(array), PatchOp::Remove { .. }) => {
+            let index: usize = last.parse().map_err(|_| invalid())?;
+            if index >= array.len() {
+                return Err(invalid());
+            }
+            array.remove(index);
+        }
+        _ => return Err(invalid()),
+    }
+    Ok(())
+}
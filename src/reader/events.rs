@@ -0,0 +1,311 @@
+use std::collections::VecDeque;
+use std::ops::Deref;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use crate::memory::MemorySource;
+use crate::reader::beatmap::stable::memory::md5;
+use crate::reader::common::stable::memory::{game_state, is_paused, is_watching_replay, menu_mods};
+use crate::reader::common::GameState;
+use crate::reader::gameplay::stable::memory::{combo, hp, mods, retries};
+use crate::reader::structs::State;
+use crate::reader::wait::CancellationToken;
+use crate::Error;
+use rosu_mods::GameModsLegacy;
+
+/// Something that happened in game between two polls
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    GameStateChanged {
+        from: GameState,
+        to: GameState,
+    },
+    BeatmapChanged {
+        md5: String,
+    },
+    PlayStarted,
+    PlayRetried,
+    /// HP reached zero during a play with mods that can fail
+    PlayFailed,
+    /// A play (not a replay or spectated one) ended on a result screen
+    PlayPassed,
+    Paused,
+    Resumed,
+    /// Combo dropped, `combo` is the combo that was lost
+    ComboBroken {
+        combo: i16,
+    },
+    MenuModsChanged {
+        mods: GameModsLegacy,
+    },
+    /// A replay or spectated play started. Can follow `PlayStarted` by a
+    /// tick when osu! sets its replay flag late
+    ReplayStarted,
+}
+
+/// Per play values, reset whenever a play starts or is retried
+#[derive(Debug, Default)]
+struct PlayTracker {
+    retries: Option<i32>,
+    paused: bool,
+    combo: i16,
+    /// HP was seen above zero, it reads as zero while the play loads
+    alive: bool,
+    failed: bool,
+    /// Watching a replay or spectating, osu! plays both as replays
+    replay: bool,
+    /// Gameplay mods, read once HP reaches zero
+    mods: Option<GameModsLegacy>,
+}
+
+/// Mods with which HP reaching zero doesn't fail the play
+const NO_FAIL_MODS: GameModsLegacy = GameModsLegacy::NoFail
+    .union(GameModsLegacy::Relax)
+    .union(GameModsLegacy::Autopilot)
+    .union(GameModsLegacy::Autoplay)
+    .union(GameModsLegacy::Cinema);
+
+/// Result screens a play can end on
+const RESULT_SCREENS: [GameState; 4] = [
+    GameState::ResultScreen,
+    GameState::MultiplayerResultScreen,
+    GameState::MultiplayerResultScreenTagCoop,
+    GameState::MultiplayerResultScreenTeamVs,
+];
+
+/// Polls the readers and turns value changes into [`GameEvent`]s.
+///
+/// Owns its memory source and `State`, pass `&process` to keep using the
+/// process elsewhere.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rosu_memory_lib::init_loop;
+/// use rosu_memory_lib::reader::events::EventPoller;
+///
+/// let (state, process) = init_loop(500)?;
+/// for event in EventPoller::new(&process, state) {
+///     println!("{:?}", event?);
+/// }
+/// # Ok::<(), rosu_memory_lib::Error>(())
+/// ```
+pub struct EventPoller<P: MemorySource> {
+    process: P,
+    state: State,
+    interval: Duration,
+    game_state: Option<GameState>,
+    md5: Option<String>,
+    menu_mods: Option<GameModsLegacy>,
+    play: PlayTracker,
+    queue: VecDeque<GameEvent>,
+}
+
+impl<P: MemorySource> EventPoller<P> {
+    pub fn new(process: P, state: State) -> Self {
+        Self {
+            process,
+            state,
+            interval: Duration::from_millis(50),
+            game_state: None,
+            md5: None,
+            menu_mods: None,
+            play: PlayTracker::default(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Time slept between polls when iterating, defaults to 50ms
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Reads the game once and returns every event since the previous poll.
+    ///
+    /// Only a failure to read the game state is reported, other values are
+    /// skipped for this tick since they are routinely unavailable while osu!
+    /// switches screens.
+    pub fn poll(&mut self) -> Result<Vec<GameEvent>, Error> {
+        let p = &self.process;
+        let state = &mut self.state;
+        let mut events = Vec::new();
+
+        let current = game_state(p, state)?;
+        let previous = self.game_state.replace(current);
+        if previous != Some(current) {
+            let from = previous.unwrap_or_default();
+            events.push(GameEvent::GameStateChanged { from, to: current });
+
+            if from == GameState::Playing
+                && RESULT_SCREENS.contains(&current)
+                && !self.play.replay
+                && !self.play.failed
+            {
+                events.push(GameEvent::PlayPassed);
+            }
+            if current == GameState::Playing {
+                let replay = is_watching_replay(p, state).unwrap_or(false);
+                self.play = PlayTracker {
+                    replay,
+                    ..Default::default()
+                };
+                if replay {
+                    events.push(GameEvent::ReplayStarted);
+                } else {
+                    events.push(GameEvent::PlayStarted);
+                }
+            }
+        }
+
+        if let Ok(new_md5) = md5(p, state) {
+            if !new_md5.is_empty() && self.md5.as_ref() != Some(&new_md5) {
+                self.md5 = Some(new_md5.clone());
+                events.push(GameEvent::BeatmapChanged { md5: new_md5 });
+            }
+        }
+
+        if let Ok(mods) = menu_mods(p, state) {
+            if self.menu_mods.replace(mods).is_some_and(|old| old != mods) {
+                events.push(GameEvent::MenuModsChanged { mods });
+            }
+        }
+
+        if current == GameState::Playing {
+            let play = &mut self.play;
+            let mut retried = false;
+
+            if let Ok(new_retries) = retries(p, state) {
+                if play
+                    .retries
+                    .replace(new_retries)
+                    .is_some_and(|old| new_retries > old)
+                {
+                    retried = true;
+                    *play = PlayTracker {
+                        retries: Some(new_retries),
+                        replay: play.replay,
+                        ..Default::default()
+                    };
+                    events.push(GameEvent::PlayRetried);
+                }
+            }
+
+            // the flag can lag behind the game state by a tick, a play can't
+            // turn into a replay so the start was one all along
+            if !play.replay && is_watching_replay(p, state).unwrap_or(false) {
+                play.replay = true;
+                events.push(GameEvent::ReplayStarted);
+            }
+
+            if let Ok(paused) = is_paused(p, state) {
+                if paused != play.paused {
+                    play.paused = paused;
+                    events.push(if paused {
+                        GameEvent::Paused
+                    } else {
+                        GameEvent::Resumed
+                    });
+                }
+            }
+
+            if let Ok(new_combo) = combo(p, state) {
+                if !retried && new_combo < play.combo {
+                    events.push(GameEvent::ComboBroken { combo: play.combo });
+                }
+                play.combo = new_combo;
+            }
+
+            if let Ok(hp) = hp(p, state) {
+                if hp > 0.0 {
+                    play.alive = true;
+                } else if play.alive && !play.failed {
+                    if play.mods.is_none() {
+                        play.mods = mods(p, state).ok();
+                    }
+                    if play.mods.is_some_and(|mods| !mods.intersects(NO_FAIL_MODS)) {
+                        play.failed = true;
+                        events.push(GameEvent::PlayFailed);
+                    }
+                }
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+impl<P: MemorySource + Send + 'static> EventPoller<P> {
+    /// Moves the poller to a background thread and returns the receiving end,
+    /// the thread stops once the receiver is dropped or a poll fails
+    pub fn into_channel(mut self) -> EventReceiver {
+        let (tx, rx) = mpsc::channel();
+        let stop = CancellationToken::new();
+        let token = stop.clone();
+        std::thread::spawn(move || loop {
+            match self.poll() {
+                Ok(events) => {
+                    if events.into_iter().any(|event| tx.send(Ok(event)).is_err()) {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    break;
+                }
+            }
+            if token.sleep(self.interval) {
+                break;
+            }
+        });
+        EventReceiver { rx, stop }
+    }
+}
+
+/// Receiving end of [`EventPoller::into_channel`], dropping it stops the
+/// polling thread even if no event is pending
+pub struct EventReceiver {
+    rx: Receiver<Result<GameEvent, Error>>,
+    stop: CancellationToken,
+}
+
+impl Deref for EventReceiver {
+    type Target = Receiver<Result<GameEvent, Error>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rx
+    }
+}
+
+impl Iterator for EventReceiver {
+    type Item = Result<GameEvent, Error>;
+
+    /// Blocks until the next event, `None` once the polling thread stopped
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rx.recv().ok()
+    }
+}
+
+impl Drop for EventReceiver {
+    fn drop(&mut self) {
+        self.stop.cancel();
+    }
+}
+
+impl<P: MemorySource> Iterator for EventPoller<P> {
+    type Item = Result<GameEvent, Error>;
+
+    /// Blocks until the next event, polling every `interval`
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.queue.pop_front() {
+                return Some(Ok(event));
+            }
+            match self.poll() {
+                Ok(events) if events.is_empty() => std::thread::sleep(self.interval),
+                Ok(events) => self.queue.extend(events),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
@@ -1,9 +1,13 @@
+use crate::reader::common::GameMode;
+use crate::reader::grade::Grade;
 use crate::reader::structs::Hit;
+use rosu_mods::GameModsLegacy;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameplayInfo {
     pub score: i32,
-    pub mods: u32,
+    pub mods: GameModsLegacy,
     pub combo: i16,
     pub max_combo: i16,
     pub hp: f64,
@@ -11,4 +15,11 @@ pub struct GameplayInfo {
     pub ig_time: i32,
     pub retries: i32,
     pub hits: Hit,
+    /// Mode being played, converts included
+    pub mode: GameMode,
+    pub accuracy: f64,
+    /// Accuracy with ScoreV2 and in lazer
+    pub accuracy_v2: f64,
+    /// Grade of the hits so far, doesn't turn into `F` on fail
+    pub grade: Grade,
 }
@@ -2,20 +2,23 @@ pub mod common;
 pub mod stable;
 
 use crate::impl_osu_accessor;
-use crate::reader::common::OsuClientKind;
+use crate::memory::MemorySource;
+use crate::reader::common::{GameMode, OsuClientKind};
 use crate::reader::gameplay::common::GameplayInfo;
+use crate::reader::grade::Grade;
 use crate::reader::structs::Hit;
-use crate::reader::structs::State;
+use crate::reader::structs::{State, StateHandle};
 use crate::Error;
 use rosu_mem::process::Process;
-pub struct GameplayReader<'a> {
-    pub process: &'a Process,
-    pub state: &'a mut State,
+use rosu_mods::GameModsLegacy;
+pub struct GameplayReader<'a, P: MemorySource = Process, S: StateHandle = &'a mut State> {
+    pub process: &'a P,
+    pub state: S,
     pub osu_type: OsuClientKind,
 }
 
-impl<'a> GameplayReader<'a> {
-    pub fn new(p: &'a Process, state: &'a mut State, osu_type: OsuClientKind) -> Self {
+impl<'a, P: MemorySource, S: StateHandle> GameplayReader<'a, P, S> {
+    pub fn new(p: &'a P, state: S, osu_type: OsuClientKind) -> Self {
         Self {
             process: p,
             state,
@@ -24,7 +27,7 @@ impl<'a> GameplayReader<'a> {
     }
     impl_osu_accessor! {
         fn score() -> i32 => stable::memory::score,
-        fn mods() -> u32 => stable::memory::mods,
+        fn mods() -> GameModsLegacy => stable::memory::mods,
         fn combo() -> i16 => stable::memory::combo,
         fn max_combo() -> i16 => stable::memory::max_combo,
         fn hp() -> f64 => stable::memory::hp,
@@ -38,6 +41,10 @@ impl<'a> GameplayReader<'a> {
         fn hits_miss() -> i16 => stable::memory::hits_miss,
         fn hits_geki() -> i16 => stable::memory::hits_geki,
         fn hits_katu() -> i16 => stable::memory::hits_katu,
+        fn mode() -> GameMode => stable::memory::mode,
+        fn accuracy() -> f64 => stable::memory::accuracy,
+        fn accuracy_v2() -> f64 => stable::memory::accuracy_v2,
+        fn grade() -> Grade => stable::memory::grade,
         fn info() -> GameplayInfo => stable::memory::info,
 
     }
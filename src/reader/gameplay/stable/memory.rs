@@ -1,121 +1,142 @@
+use crate::memory::MemorySource;
 use crate::reader::common::stable::memory::check_game_state;
-use crate::reader::common::GameState;
+use crate::reader::common::{GameMode, GameState};
 use crate::reader::gameplay::common::GameplayInfo;
-use crate::reader::gameplay::stable::offset::GAMEPLAY_OFFSET;
+use crate::reader::grade::{self, calculate_accuracy, calculate_accuracy_v2, Grade};
+use crate::reader::offsets::offsets;
 use crate::reader::structs::Hit;
-use crate::reader::structs::State;
+use crate::reader::structs::{SignatureName, State};
 use crate::Error;
 use crate::{
     generate_offset_getter,
-    reader::helpers::{read_f64, read_i16, read_i32, read_string, read_u64},
+    reader::helpers::{read_f64, read_hits, read_i16, read_i32, read_string, read_u64},
 };
-use rosu_mem::process::{Process, ProcessTraits};
-use std::mem::size_of;
+use rosu_mods::GameModsLegacy;
 
-pub fn rulesets_addr(p: &Process, state: &mut State) -> Result<i32, Error> {
+pub fn rulesets_addr<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
     if check_game_state(p, state, GameState::Playing)? {
-        Ok(p.read_i32(state.addresses.rulesets - GAMEPLAY_OFFSET.ptr)?)
+        rulesets_addr_unchecked(p, state)
     } else {
         Err(Error::NotAvailable("Not in Playing".to_string()))
     }
 }
 
-pub fn mods(p: &Process, state: &mut State) -> Result<u32, Error> {
+/// Same as `rulesets_addr` for callers that already checked the game state
+pub(crate) fn rulesets_addr_unchecked<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+) -> Result<i32, Error> {
+    p.read_i32(state.address(p, SignatureName::Rulesets)? - offsets().gameplay.ptr)
+}
+
+pub fn mods<P: MemorySource>(p: &P, state: &mut State) -> Result<GameModsLegacy, Error> {
     let mods_xor1 = mods_xor1(p, state)?;
     let mods_xor2 = mods_xor2(p, state)?;
-    Ok((mods_xor1 ^ mods_xor2) as u32)
+    Ok(GameModsLegacy::from_bits((mods_xor1 ^ mods_xor2) as u32))
 }
 
 generate_offset_getter! {
-    ruleset_addr: i32 = read_i32(GAMEPLAY_OFFSET.addr, rulesets_addr);
-    gameplay_base: i32 = read_i32(GAMEPLAY_OFFSET.base, ruleset_addr);
-    score_base: i32 = read_i32(GAMEPLAY_OFFSET.score_base, gameplay_base);
-    hp_base: i32 = read_i32(GAMEPLAY_OFFSET.hp_base, gameplay_base);
-    score: i32 = read_i32(GAMEPLAY_OFFSET.score, score_base);
-    mods_xor_base: i32 = read_i32(GAMEPLAY_OFFSET.mods, score_base);
-    mods_xor1: u64 = read_u64(GAMEPLAY_OFFSET.mods_xor, mods_xor_base);
-    mods_xor2: u64 = read_u64(GAMEPLAY_OFFSET.mods_xor2, mods_xor_base);
-    combo: i16 = read_i16(GAMEPLAY_OFFSET.combo, score_base);
-    max_combo: i16 = read_i16(GAMEPLAY_OFFSET.max_combo, score_base);
-    hp: f64 = read_f64(GAMEPLAY_OFFSET.hp, hp_base);
-    username: String = read_string(GAMEPLAY_OFFSET.username, score_base);
-    hits_300: i16 = read_i16(GAMEPLAY_OFFSET.hits._300, score_base);
-    hits_100: i16 = read_i16(GAMEPLAY_OFFSET.hits._100, score_base);
-    hits_50: i16 = read_i16(GAMEPLAY_OFFSET.hits._50, score_base);
-    hits_miss: i16 = read_i16(GAMEPLAY_OFFSET.hits._miss, score_base);
-    hits_geki: i16 = read_i16(GAMEPLAY_OFFSET.hits._geki, score_base);
-    hits_katu: i16 = read_i16(GAMEPLAY_OFFSET.hits._katu, score_base);
+    ruleset_addr: i32 = read_i32(offsets().gameplay.addr, rulesets_addr);
+    gameplay_base: i32 = read_i32(offsets().gameplay.base, ruleset_addr);
+    score_base: i32 = read_i32(offsets().gameplay.score_base, gameplay_base);
+    hp_base: i32 = read_i32(offsets().gameplay.hp_base, gameplay_base);
+    score: i32 = read_i32(offsets().gameplay.score, score_base);
+    mods_xor_base: i32 = read_i32(offsets().gameplay.mods, score_base);
+    mods_xor1: u64 = read_u64(offsets().gameplay.mods_xor, mods_xor_base);
+    mods_xor2: u64 = read_u64(offsets().gameplay.mods_xor2, mods_xor_base);
+    combo: i16 = read_i16(offsets().gameplay.combo, score_base);
+    max_combo: i16 = read_i16(offsets().gameplay.max_combo, score_base);
+    hp: f64 = read_f64(offsets().gameplay.hp, hp_base);
+    username: String = read_string(offsets().gameplay.username, score_base);
+    hits_300: i16 = read_i16(offsets().gameplay.hits._300, score_base);
+    hits_100: i16 = read_i16(offsets().gameplay.hits._100, score_base);
+    hits_50: i16 = read_i16(offsets().gameplay.hits._50, score_base);
+    hits_miss: i16 = read_i16(offsets().gameplay.hits._miss, score_base);
+    hits_geki: i16 = read_i16(offsets().gameplay.hits._geki, score_base);
+    hits_katu: i16 = read_i16(offsets().gameplay.hits._katu, score_base);
 }
 
 /// this is a wrapper to not confuse people it could be deleted in the future
 /// use -> crate::reader::common::stable::memory::game_time
-pub fn game_time(p: &Process, state: &mut State) -> Result<i32, Error> {
+pub fn game_time<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
     crate::reader::common::stable::memory::game_time(p, state)
 }
 
-pub fn retries(p: &Process, state: &mut State) -> Result<i32, Error> {
-    let igt_addr = p.read_i32(state.addresses.base - GAMEPLAY_OFFSET.ruleset)?;
-    let retries = p.read_i32(igt_addr + GAMEPLAY_OFFSET.retries)?;
+pub fn retries<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
+    let igt_addr =
+        p.read_i32(state.address(p, SignatureName::Base)? - offsets().gameplay.ruleset)?;
+    let retries = p.read_i32(igt_addr + offsets().gameplay.retries)?;
     Ok(retries)
 }
 
-pub fn hits(p: &Process, state: &mut State) -> Result<Hit, Error> {
-    let score_base = score_base(p, state)?;
+/// Mode being played, which is the one picked in song select
+/// use -> crate::reader::common::stable::memory::menu_ruleset
+pub fn mode<P: MemorySource>(p: &P, state: &mut State) -> Result<GameMode, Error> {
+    crate::reader::common::stable::memory::menu_ruleset(p, state)
+}
 
-    // Read all hits data in one memory operation
-    let mut hits_buffer = [0u8; size_of::<i16>() * 6];
-    p.read(
-        score_base + GAMEPLAY_OFFSET.hits._100,
-        size_of::<i16>() * 6,
-        &mut hits_buffer,
-    )?;
-
-    // Safety: unwrap here because buffer is already initialized and filled
-    // with zeros, the worst case scenario is hits going to be zeros
-    Ok(Hit {
-        _100: i16::from_le_bytes(hits_buffer[0..2].try_into().unwrap()),
-        _300: i16::from_le_bytes(hits_buffer[2..4].try_into().unwrap()),
-        _50: i16::from_le_bytes(hits_buffer[4..6].try_into().unwrap()),
-        _geki: i16::from_le_bytes(hits_buffer[6..8].try_into().unwrap()),
-        _katu: i16::from_le_bytes(hits_buffer[8..10].try_into().unwrap()),
-        _miss: i16::from_le_bytes(hits_buffer[10..12].try_into().unwrap()),
-    })
+pub fn accuracy<P: MemorySource>(p: &P, state: &mut State) -> Result<f64, Error> {
+    calculate_accuracy(&mode(p, state)?, &hits(p, state)?)
+}
+
+/// Accuracy with ScoreV2 and in lazer, see [`calculate_accuracy_v2`]
+pub fn accuracy_v2<P: MemorySource>(p: &P, state: &mut State) -> Result<f64, Error> {
+    calculate_accuracy_v2(&mode(p, state)?, &hits(p, state)?)
 }
 
-pub fn info(p: &Process, state: &mut State) -> Result<GameplayInfo, Error> {
+/// Grade of the hits so far, with silver ranks for HD/FL
+pub fn grade<P: MemorySource>(p: &P, state: &mut State) -> Result<Grade, Error> {
+    Ok(grade::grade(
+        &mode(p, state)?,
+        &hits(p, state)?,
+        mods(p, state)?,
+    ))
+}
+
+pub fn hits<P: MemorySource>(p: &P, state: &mut State) -> Result<Hit, Error> {
     let score_base = score_base(p, state)?;
+    read_hits(p, score_base + offsets().gameplay.hits._100)
+}
+
+pub fn info<P: MemorySource>(p: &P, state: &mut State) -> Result<GameplayInfo, Error> {
+    let gameplay_base = gameplay_base(p, state)?;
+    info_at(p, state, gameplay_base)
+}
+
+/// Gameplay info starting from an already resolved `gameplay_base`, every
+/// field is read from it or from a static address
+pub(crate) fn info_at<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+    gameplay_base: i32,
+) -> Result<GameplayInfo, Error> {
+    let offsets = offsets();
+    let score_base = p.read_i32(gameplay_base + offsets.gameplay.score_base)?;
+    let hp_base = p.read_i32(gameplay_base + offsets.gameplay.hp_base)?;
+
+    let hp = p.read_f64(hp_base + offsets.gameplay.hp)?;
+    let mods_xor_base = p.read_i32(score_base + offsets.gameplay.mods)?;
+    let mods = GameModsLegacy::from_bits(
+        (p.read_u64(mods_xor_base + offsets.gameplay.mods_xor)?
+            ^ p.read_u64(mods_xor_base + offsets.gameplay.mods_xor2)?) as u32,
+    );
 
-    let hp = hp(p, state)?;
-    let mods = mods(p, state)?;
-
-    // Read all hits data in one memory operation
-    let mut hits_buffer = [0u8; size_of::<i16>() * 6];
-    p.read(
-        score_base + GAMEPLAY_OFFSET.hits._100,
-        size_of::<i16>() * 6,
-        &mut hits_buffer,
-    )?;
-
-    // Safety: unwrap here because buffer is already initialized and filled
-    // with zeros, the worst case scenario is hits going to be zeros
-    let hits = Hit {
-        _100: i16::from_le_bytes(hits_buffer[0..2].try_into().unwrap()),
-        _300: i16::from_le_bytes(hits_buffer[2..4].try_into().unwrap()),
-        _50: i16::from_le_bytes(hits_buffer[4..6].try_into().unwrap()),
-        _geki: i16::from_le_bytes(hits_buffer[6..8].try_into().unwrap()),
-        _katu: i16::from_le_bytes(hits_buffer[8..10].try_into().unwrap()),
-        _miss: i16::from_le_bytes(hits_buffer[10..12].try_into().unwrap()),
-    };
+    let hits = read_hits(p, score_base + offsets.gameplay.hits._100)?;
+    let mode = mode(p, state)?; // different base
 
     Ok(GameplayInfo {
-        score: p.read_i32(score_base + GAMEPLAY_OFFSET.score)?,
+        score: p.read_i32(score_base + offsets.gameplay.score)?,
         mods,
-        combo: p.read_i16(score_base + GAMEPLAY_OFFSET.combo)?,
-        max_combo: p.read_i16(score_base + GAMEPLAY_OFFSET.max_combo)?,
+        combo: p.read_i16(score_base + offsets.gameplay.combo)?,
+        max_combo: p.read_i16(score_base + offsets.gameplay.max_combo)?,
         hp,
-        username: p.read_string(score_base + GAMEPLAY_OFFSET.username)?,
+        username: p.read_string(score_base + offsets.gameplay.username)?,
         ig_time: game_time(p, state)?, // different base
         retries: retries(p, state)?,   // different base
+        accuracy: calculate_accuracy(&mode, &hits)?,
+        accuracy_v2: calculate_accuracy_v2(&mode, &hits)?,
+        grade: grade::grade(&mode, &hits, mods),
         hits,
+        mode,
     })
 }
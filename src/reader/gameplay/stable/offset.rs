@@ -1,3 +1,9 @@
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct GameplayOffset {
     pub ptr: i32,
     pub addr: i32,
@@ -36,6 +42,12 @@ pub const GAMEPLAY_OFFSET: GameplayOffset = GameplayOffset {
     hits: GAMEPLAY_HITS_OFFSET,
 };
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct GameplayHitsOffset {
     pub _300: i32,
     pub _100: i32,
@@ -53,3 +65,15 @@ pub const GAMEPLAY_HITS_OFFSET: GameplayHitsOffset = GameplayHitsOffset {
     _katu: 0x90, // 144
     _miss: 0x92, // 146
 };
+
+impl Default for GameplayOffset {
+    fn default() -> Self {
+        GAMEPLAY_OFFSET
+    }
+}
+
+impl Default for GameplayHitsOffset {
+    fn default() -> Self {
+        GAMEPLAY_HITS_OFFSET
+    }
+}
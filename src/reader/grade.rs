@@ -0,0 +1,204 @@
+//! Accuracy and letter grades, computed from hits the same way osu! does.
+//! Shared by the gameplay and result screen readers
+use std::fmt;
+
+use crate::reader::common::GameMode;
+use crate::reader::structs::Hit;
+use crate::Error;
+use rosu_mods::GameModsLegacy;
+
+/// Serialized as the variant name (`"SSH"`)
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Grade {
+    /// Silver SS, with HD or FL (or FI in osu!mania)
+    SSH,
+    SS,
+    /// Silver S
+    SH,
+    S,
+    A,
+    B,
+    C,
+    #[default]
+    D,
+    /// Failed play
+    F,
+}
+
+impl Grade {
+    pub fn is_silver(&self) -> bool {
+        matches!(self, Grade::SSH | Grade::SH)
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Grade::SSH => "SSH",
+            Grade::SS => "SS",
+            Grade::SH => "SH",
+            Grade::S => "S",
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::D => "D",
+            Grade::F => "F",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Hit count as f64, counts are summed after conversion so long maps can't
+/// overflow the i16 osu! stores them as
+fn count(n: i16) -> f64 {
+    n.max(0) as f64
+}
+
+/// Accuracy as shown by osu!stable, from 0 to 100
+#[inline]
+pub fn calculate_accuracy(gamemode: &GameMode, hit: &Hit) -> Result<f64, Error> {
+    let acc = match gamemode {
+        GameMode::Osu => {
+            let total = count(hit._300) + count(hit._100) + count(hit._50) + count(hit._miss);
+            if total == 0.0 {
+                return Ok(0.0);
+            }
+            let score = count(hit._300) * 6.0 + count(hit._100) * 2.0 + count(hit._50);
+            (score / (total * 6.0)) * 100.0
+        }
+        GameMode::Taiko => {
+            let total = count(hit._300) + count(hit._100) + count(hit._50) + count(hit._miss);
+            if total == 0.0 {
+                return Ok(0.0);
+            }
+            let score = count(hit._300) * 2.0 + count(hit._100);
+            (score / (total * 2.0)) * 100.0
+        }
+        GameMode::Catch => {
+            let caught = count(hit._300) + count(hit._100) + count(hit._50);
+            let total = caught + count(hit._katu) + count(hit._miss);
+            if total == 0.0 {
+                return Ok(0.0);
+            }
+            (caught / total) * 100.0
+        }
+        GameMode::Mania => {
+            let total = judged(hit);
+            if total == 0.0 {
+                return Ok(0.0);
+            }
+            let score = (count(hit._geki) + count(hit._300)) * 6.0
+                + count(hit._katu) * 4.0
+                + count(hit._100) * 2.0
+                + count(hit._50);
+            (score / (total * 6.0)) * 100.0
+        }
+        _ => return Ok(0.0),
+    };
+
+    Ok(acc)
+}
+
+/// Accuracy with ScoreV2 and in lazer, from 0 to 100. Only osu!mania differs
+/// from [`calculate_accuracy`]: perfects (geki) are worth 305 instead of 300
+#[inline]
+pub fn calculate_accuracy_v2(gamemode: &GameMode, hit: &Hit) -> Result<f64, Error> {
+    if *gamemode != GameMode::Mania {
+        return calculate_accuracy(gamemode, hit);
+    }
+    let total = judged(hit);
+    if total == 0.0 {
+        return Ok(0.0);
+    }
+    let score = count(hit._geki) * 305.0
+        + count(hit._300) * 300.0
+        + count(hit._katu) * 200.0
+        + count(hit._100) * 100.0
+        + count(hit._50) * 50.0;
+    Ok((score / (total * 305.0)) * 100.0)
+}
+
+/// Every judgement, including the ones only some modes use
+fn judged(hit: &Hit) -> f64 {
+    count(hit._geki)
+        + count(hit._300)
+        + count(hit._katu)
+        + count(hit._100)
+        + count(hit._50)
+        + count(hit._miss)
+}
+
+/// Grade of a passed play with `hits` so far, `SS` when nothing was hit yet.
+/// With ScoreV2, osu!mania grades use [`calculate_accuracy_v2`]
+pub fn grade(gamemode: &GameMode, hits: &Hit, mods: GameModsLegacy) -> Grade {
+    if judged(hits) == 0.0 {
+        return silver(Grade::SS, gamemode, mods);
+    }
+
+    let grade = match gamemode {
+        GameMode::Catch => by_accuracy(gamemode, hits, mods, [98.0, 94.0, 90.0, 85.0]),
+        GameMode::Mania => by_accuracy(gamemode, hits, mods, [95.0, 90.0, 80.0, 70.0]),
+        _ => {
+            let total = count(hits._300) + count(hits._100) + count(hits._50) + count(hits._miss);
+            let ratio_300 = count(hits._300) / total;
+            let ratio_50 = count(hits._50) / total;
+            let no_miss = hits._miss <= 0;
+
+            if total == 0.0 || ratio_300 == 1.0 {
+                Grade::SS
+            } else if ratio_300 > 0.9 && ratio_50 <= 0.01 && no_miss {
+                Grade::S
+            } else if (ratio_300 > 0.8 && no_miss) || ratio_300 > 0.9 {
+                Grade::A
+            } else if (ratio_300 > 0.7 && no_miss) || ratio_300 > 0.8 {
+                Grade::B
+            } else if ratio_300 > 0.6 {
+                Grade::C
+            } else {
+                Grade::D
+            }
+        }
+    };
+    silver(grade, gamemode, mods)
+}
+
+/// Accuracy thresholds for S, A, B and C, anything lower is a D
+fn by_accuracy(
+    gamemode: &GameMode,
+    hits: &Hit,
+    mods: GameModsLegacy,
+    [s, a, b, c]: [f64; 4],
+) -> Grade {
+    let accuracy = if mods.contains(GameModsLegacy::ScoreV2) {
+        calculate_accuracy_v2(gamemode, hits)
+    } else {
+        calculate_accuracy(gamemode, hits)
+    }
+    .unwrap_or_default();
+    if accuracy >= 100.0 {
+        Grade::SS
+    } else if accuracy > s {
+        Grade::S
+    } else if accuracy > a {
+        Grade::A
+    } else if accuracy > b {
+        Grade::B
+    } else if accuracy > c {
+        Grade::C
+    } else {
+        Grade::D
+    }
+}
+
+fn silver(grade: Grade, gamemode: &GameMode, mods: GameModsLegacy) -> Grade {
+    let mut hiding = GameModsLegacy::Hidden | GameModsLegacy::Flashlight;
+    if *gamemode == GameMode::Mania {
+        hiding |= GameModsLegacy::FadeIn;
+    }
+    match grade {
+        Grade::SS if mods.intersects(hiding) => Grade::SSH,
+        Grade::S if mods.intersects(hiding) => Grade::SH,
+        grade => grade,
+    }
+}
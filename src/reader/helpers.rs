@@ -1,8 +1,8 @@
+use crate::memory::MemorySource;
 use crate::reader::common::GameMode;
-use crate::reader::structs::Hit;
-use crate::reader::structs::State;
+use crate::reader::structs::{Hit, State};
 use crate::Error;
-use rosu_mem::process::{Process, ProcessTraits};
+use rosu_mods::{GameMods, GameModsLegacy};
 
 /// Generates standardized memory reading functions.
 ///
@@ -11,7 +11,7 @@ use rosu_mem::process::{Process, ProcessTraits};
 ///
 /// # Syntax
 ///
-/// ```rust
+/// ```ignore
 /// generate_reader_fn! {
 ///     function_name, return_type, read_method
 /// }
@@ -21,11 +21,11 @@ use rosu_mem::process::{Process, ProcessTraits};
 ///
 /// * `function_name` - Name of the generated function most likely read_<type>
 /// * `return_type` - Rust type to return (e.g., `i32`, `String`, `f64`)
-/// * `read_method` - Method name on Process trait (e.g., `read_i32`, `read_string`) most of the time from rosu_mem
+/// * `read_method` - Method name on the `MemorySource` trait (e.g., `read_i32`, `read_string`)
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```ignore
 /// generate_reader_fn! {
 ///     read_score, i32, read_i32
 /// }
@@ -35,14 +35,14 @@ macro_rules! generate_reader_fn {
     (
         $name:ident, $ret_ty:ty, $read_fn:ident
     ) => {
-        pub(crate) fn $name(
-            p: &Process,
+        pub(crate) fn $name<P: MemorySource>(
+            p: &P,
             state: &mut State,
             offset: i32,
-            get_base_addr: fn(&Process, &mut State) -> Result<i32, Error>,
+            get_base_addr: fn(&P, &mut State) -> Result<i32, Error>,
         ) -> Result<$ret_ty, Error> {
             let base_addr = get_base_addr(p, state)?;
-            Ok(p.$read_fn(base_addr + offset)?)
+            p.$read_fn(base_addr + offset)
         }
     };
 }
@@ -54,7 +54,7 @@ macro_rules! generate_reader_fn {
 ///
 /// # Syntax
 ///
-/// ```rust
+/// ```ignore
 /// generate_offset_getter! {
 ///     function_name: return_type = read_method(offset, base_getter);
 ///     another_function: another_type = another_read_method(another_offset, another_base);
@@ -72,7 +72,7 @@ macro_rules! generate_reader_fn {
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```ignore
 /// generate_offset_getter! {
 ///     score: i32 = read_i32(0x10, score_base),
 ///     combo: i16 = read_i16(0x14, score_base),
@@ -81,7 +81,7 @@ macro_rules! generate_reader_fn {
 /// }
 ///
 /// // Generates functions like:
-/// // pub fn score(p: &Process, state: &mut State) -> Result<i32, Error> {
+/// // pub fn score<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
 /// //     Ok(<i32>::from(read_i32(p, state, 0x10, score_base)?))
 /// // }
 /// ```
@@ -89,8 +89,8 @@ macro_rules! generate_reader_fn {
 /// # Generated Functions
 ///
 /// Each definition generates a function with signature:
-/// ```rust
-/// pub fn function_name(p: &Process, state: &mut State) -> Result<return_type, Error>
+/// ```ignore
+/// pub fn function_name<P: MemorySource>(p: &P, state: &mut State) -> Result<return_type, Error>
 /// ```
 ///
 /// # Memory Safety
@@ -103,7 +103,10 @@ macro_rules! generate_offset_getter {
         $( $fn_name:ident : $ret_ty:ty = $read_fn:ident ( $offset:expr , $get_base:ident ); )*
     ) => {
         $(
-            pub fn $fn_name(p: &Process, state: &mut State) -> Result<$ret_ty, Error> {
+            pub fn $fn_name<P: $crate::memory::MemorySource>(
+                p: &P,
+                state: &mut State,
+            ) -> Result<$ret_ty, Error> {
                 Ok(<$ret_ty>::from($read_fn(p, state, $offset, $get_base)?))
             }
         )*
@@ -118,7 +121,7 @@ macro_rules! generate_offset_getter {
 ///
 /// # Syntax
 ///
-/// ```rust
+/// ```ignore
 /// impl_osu_accessor! {
 ///     fn method_name() -> return_type => implementation_path,
 ///     fn another_method() -> another_type => another_implementation,
@@ -133,11 +136,11 @@ macro_rules! generate_offset_getter {
 ///
 /// # Examples
 ///
-/// ```rust
-/// impl<'a> CommonReader<'a> {
+/// ```ignore
+/// impl<'a, P: MemorySource, S: StateHandle> CommonReader<'a, P, S> {
 ///     impl_osu_accessor! {
 ///         fn game_state() -> GameState => stable::memory::game_state,
-///         fn menu_game_mode() -> GameMode => stable::memory::menu_game_mode,
+///         fn menu_ruleset() -> GameMode => stable::memory::menu_ruleset,
 ///         fn path_folder() -> PathBuf => stable::memory::path_folder,
 ///     }
 /// }
@@ -146,10 +149,13 @@ macro_rules! generate_offset_getter {
 /// # Generated Methods
 ///
 /// For each definition, generates a method like:
-/// ```rust
+/// ```ignore
 /// pub fn method_name(&mut self) -> Result<return_type, Error> {
 ///     match self.osu_type {
-///         OsuClientKind::Stable => implementation_path(self.process, self.state),
+///         OsuClientKind::Stable => {
+///             let process = self.process;
+///             self.state.with_state(|state| implementation_path(process, state))
+///         }
 ///         _ => Err(Error::Unsupported("Unsupported osu type for now".to_string())),
 ///     }
 /// }
@@ -177,7 +183,12 @@ macro_rules! impl_osu_accessor {
         $(
             pub fn $name(&mut self) -> Result<$ret, Error> {
                 match self.osu_type {
-                    OsuClientKind::Stable => $call(self.process, self.state),
+                    OsuClientKind::Stable => {
+                        let process = self.process;
+                        $crate::reader::structs::StateHandle::with_state(&mut self.state, |state| {
+                            $call(process, state)
+                        })
+                    }
                     _ => Err(Error::Unsupported(
                         "Unsupported osu type for now".to_string(),
                     )),
@@ -187,49 +198,18 @@ macro_rules! impl_osu_accessor {
     };
 }
 
-// TODO : idk where to put this
-#[inline]
-pub fn calculate_accuracy(gamemode: &GameMode, hit: &Hit) -> Result<f64, Error> {
-    let acc = match gamemode {
-        GameMode::Osu => {
-            let total = (hit._300 + hit._100 + hit._50 + hit._miss) as f64;
-            if total == 0.0 {
-                return Ok(0.0);
-            }
-            let score = hit._300 as f64 * 6.0 + hit._100 as f64 * 2.0 + hit._50 as f64;
-            (score / (total * 6.0)) * 100.0
-        }
-        GameMode::Taiko => {
-            let total = (hit._300 + hit._100 + hit._50 + hit._miss) as f64;
-            if total == 0.0 {
-                return Ok(0.0);
-            }
-            let score = hit._300 as f64 * 2.0 + hit._100 as f64;
-            (score / (total * 2.0)) * 100.0
-        }
-        GameMode::Catch => {
-            let caught = (hit._300 + hit._100 + hit._50) as f64;
-            let total = (hit._300 + hit._100 + hit._50 + hit._katu + hit._miss) as f64;
-            if total == 0.0 {
-                return Ok(0.0);
-            }
-            (caught / total) * 100.0
-        }
-        GameMode::Mania => {
-            let total = (hit._geki + hit._300 + hit._katu + hit._100 + hit._50 + hit._miss) as f64;
-            if total == 0.0 {
-                return Ok(0.0);
-            }
-            let score = (hit._geki + hit._300) as f64 * 6.0
-                + hit._katu as f64 * 4.0
-                + hit._100 as f64 * 2.0
-                + hit._50 as f64;
-            (score / (total * 6.0)) * 100.0
-        }
-        _ => return Ok(0.0),
-    };
+pub use crate::reader::grade::calculate_accuracy;
 
-    Ok(acc)
+/// Lazer-style mods for `gamemode` (`Unknown` counts as osu!), e.g. to read
+/// the clock rate of every mod. Legacy mods can be passed to rosu-pp as is
+pub fn lazer_mods(gamemode: &GameMode, mods: GameModsLegacy) -> GameMods {
+    let mode = match gamemode {
+        GameMode::Taiko => rosu_mods::GameMode::Taiko,
+        GameMode::Catch => rosu_mods::GameMode::Catch,
+        GameMode::Mania => rosu_mods::GameMode::Mania,
+        GameMode::Osu | GameMode::Unknown => rosu_mods::GameMode::Osu,
+    };
+    mods.to_intermode().with_mode(mode)
 }
 
 // Macro usage
@@ -241,3 +221,20 @@ generate_reader_fn!(read_i64, i64, read_i64);
 generate_reader_fn!(read_u64, u64, read_u64);
 generate_reader_fn!(read_f32, f32, read_f32);
 generate_reader_fn!(read_f64, f64, read_f64);
+
+/// Reads the six hit counts stored back to back from `addr`, the address of
+/// the 100s, in one memory operation
+pub(crate) fn read_hits<P: MemorySource>(p: &P, addr: i32) -> Result<Hit, Error> {
+    let mut hits_buffer = [0u8; size_of::<i16>() * 6];
+    p.read(addr, size_of::<i16>() * 6, &mut hits_buffer)?;
+
+    let count = |i: usize| i16::from_le_bytes([hits_buffer[i * 2], hits_buffer[i * 2 + 1]]);
+    Ok(Hit {
+        _100: count(0),
+        _300: count(1),
+        _50: count(2),
+        _geki: count(3),
+        _katu: count(4),
+        _miss: count(5),
+    })
+}
@@ -1,15 +1,31 @@
 pub mod beatmap;
+pub mod cache;
 pub mod common;
+#[cfg(feature = "serde")]
+pub mod diff;
+pub mod events;
 pub mod gameplay;
+pub mod grade;
 pub mod helpers;
+pub mod offsets;
 pub mod overlay;
+pub mod pp;
 pub mod resultscreen;
+pub mod session;
+pub mod snapshot;
+pub mod supervisor;
 pub mod user;
+pub mod wait;
 
+use crate::memory::MemorySource;
+use crate::reader::cache::SignatureCache;
+use crate::reader::common::stable::file::version_from_dir;
 use crate::reader::common::stable::memory::game_state;
 use crate::reader::common::GameState;
+use crate::reader::offsets::{set_offsets, OffsetProfiles};
 use crate::reader::structs::State;
 use crate::reader::structs::StaticAddresses;
+use crate::reader::wait::Waiter;
 use crate::Error;
 use rosu_mem::process::{Process, ProcessTraits};
 use std::time::Duration;
@@ -19,92 +35,86 @@ static EXCLUDE_WORDS: [&str; 2] = ["umu-run", "waitforexitandrun"];
 
 #[allow(dead_code)]
 // Use this function to make callback and get anything you need such as map info or user info or even submit shit
-pub fn waiting_for_gamestate<F>(
-    p: &Process,
+// For timeouts, cancellation or waiting on several states use `wait::Waiter` directly
+pub fn waiting_for_gamestate<P: MemorySource, F>(
+    p: &P,
     state: &mut State,
     g_state: GameState,
     callback: Option<F>,
 ) -> Result<(), Error>
 where
-    F: Fn(&Process, &mut State) -> Result<(), Error>,
+    F: Fn(&P, &mut State) -> Result<(), Error>,
 {
-    loop {
+    Waiter::new().for_predicate(p, state, |p, state| {
         if game_state(p, state)? == g_state {
-            return Ok(());
+            return Ok(true);
         }
         if let Some(f) = &callback {
             f(p, state)?;
         }
-    }
+        Ok(false)
+    })
 }
 
 #[allow(dead_code)]
 pub fn init_loop(sleep_duration: u64) -> Result<(State, Process), Error> {
-    let mut state = State {
-        addresses: StaticAddresses::default(),
-    };
-
-    loop {
-        match Process::initialize("osu!.exe", &EXCLUDE_WORDS) {
-            Ok(p) => {
-                println!("Found process, pid - {}", p.pid);
+    init_loop_with(&Waiter::new().interval(Duration::from_millis(sleep_duration)))
+}
 
-                println!("Reading static signatures...");
-                match StaticAddresses::new(&p) {
-                    Ok(v) => {
-                        state.addresses = v;
-                        println!("Static addresses read successfully");
-                        return Ok((state, p));
-                    }
-                    Err(e) => {
-                        match e {
-                            Error::MemoryRead(msg) => {
-                                if msg.contains("Process not found") {
-                                    println!("Process not found, sleeping for {sleep_duration}ms");
-                                    std::thread::sleep(Duration::from_millis(sleep_duration));
-                                    continue;
-                                }
-                                #[cfg(target_os = "windows")]
-                                if msg.contains("OS error") {
-                                    println!("OS error, sleeping for {sleep_duration}ms");
-                                    std::thread::sleep(Duration::from_millis(sleep_duration));
-                                    continue;
-                                }
-                                println!("Unknown error, sleeping for {sleep_duration}ms");
-                                std::thread::sleep(Duration::from_millis(sleep_duration));
-                            }
-                            _ => {
-                                println!("Unknown error, sleeping for {sleep_duration}ms");
-                                std::thread::sleep(Duration::from_millis(sleep_duration));
-                            }
-                        }
-                        println!("Unknown error, sleeping for {sleep_duration}ms");
-                        std::thread::sleep(Duration::from_millis(sleep_duration));
-                    }
-                }
-            }
-            Err(_) => {
-                println!("Unknown process error, sleeping for {sleep_duration}ms");
-                std::thread::sleep(Duration::from_millis(sleep_duration));
-            }
+/// Same as [`init_loop`] but polls according to `waiter`, so attaching can
+/// time out or be cancelled
+pub fn init_loop_with(waiter: &Waiter) -> Result<(State, Process), Error> {
+    waiter.until(|| match try_attach() {
+        Ok(attached) => Ok(Some(attached)),
+        Err(e) => {
+            tracing::debug!(error = %e, "attach failed, retrying");
+            Ok(None)
         }
-    }
+    })
+}
+
+/// Single attach attempt, fails right away if osu! is not running or its
+/// game state can't be read. Other signatures are resolved on first use, see
+/// [`StaticAddresses::lazy`]
+pub fn try_attach() -> Result<(State, Process), Error> {
+    let p = Process::initialize("osu!.exe", &EXCLUDE_WORDS)?;
+    let _span = tracing::info_span!("attach", pid = p.pid).entered();
+    tracing::info!("found osu! process");
+
+    let addresses = StaticAddresses::lazy(&p)?;
+    Ok((State { addresses }, p))
 }
 
-// Exemple of playing loop
-// pub(crate) fn playing(p: &Process, state: &mut State) -> bool {
-//     let mode_list = get_mods(p, state);
-//     while (GameState::from(get_status(p, state)) == GameState::Playing) {
-//         cur_time = reader_gameplay::get_ig_time(p, state);
-//         if (cur_time - last_time < 20 && cur_time > 0 && last_time > 0 && last_paused != cur_time) {
-//             last_paused = cur_time;
-//         }
-//         last_time = cur_time;
-//         let status = get_status(p, state);
-//         let md5 = get_beatmap_md5(p, state);
-//         if (last_retries < get_retries(p, state)) {
-//             return false;
-//         }
-//     }
-//     true
-// }
+/// Same as [`try_attach`] but signatures come from `cache` when it is still
+/// valid for the running osu! build, a full scan refreshes it otherwise
+pub fn try_attach_cached(cache: &SignatureCache) -> Result<(State, Process), Error> {
+    let p = Process::initialize("osu!.exe", &EXCLUDE_WORDS)?;
+    let _span = tracing::info_span!("attach", pid = p.pid).entered();
+    tracing::info!("found osu! process");
+
+    let addresses = cache.load_or_scan(&p)?;
+    Ok((State { addresses }, p))
+}
+
+/// Same as [`try_attach`] but first detects the osu! build and installs the
+/// matching profile from `profiles`.
+///
+/// Fails with `Error::Unsupported` when no profile covers the build, builds
+/// newer than [`LATEST_KNOWN_BUILD`](offsets::LATEST_KNOWN_BUILD) use the
+/// built-in offsets with a warning.
+pub fn try_attach_versioned(profiles: &OffsetProfiles) -> Result<(State, Process), Error> {
+    let p = Process::initialize("osu!.exe", &EXCLUDE_WORDS)?;
+    let _span = tracing::info_span!("attach", pid = p.pid).entered();
+
+    let dir = p
+        .executable_dir
+        .as_deref()
+        .ok_or_else(|| Error::NotAvailable("Executable directory is unknown".to_string()))?;
+    let version = version_from_dir(dir)?;
+    let profile = profiles.select(&version)?;
+    tracing::info!(%version, profile = profile.name, "found osu! process");
+    set_offsets(profile.offsets.clone())?;
+
+    let addresses = StaticAddresses::lazy(&p)?;
+    Ok((State { addresses }, p))
+}
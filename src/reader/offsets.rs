@@ -0,0 +1,406 @@
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use crate::reader::beatmap::common::BeatmapOffset;
+use crate::reader::common::stable::offset::CommonOffset;
+use crate::reader::common::OsuVersion;
+use crate::reader::gameplay::stable::offset::GameplayOffset;
+use crate::reader::overlay::stable::offset::KeyOverlayOffset;
+use crate::reader::resultscreen::stable::offset::ResultScreenOffset;
+use crate::reader::structs::SignatureBase;
+use crate::reader::user::stable::offset::UserProfileOffset;
+use crate::Error;
+use rosu_mem::signature::Signature;
+
+/// Anything further than this from its base is treated as a typo
+const MAX_OFFSET: i32 = 0x10000;
+
+/// Newest osu! build the built-in offsets were checked against, newer builds
+/// still use them but [`OffsetProfiles::select`] logs a warning
+pub const LATEST_KNOWN_BUILD: OsuVersion = OsuVersion {
+    date: 20250702,
+    revision: 1,
+    stream: String::new(),
+};
+
+static CURRENT: LazyLock<RwLock<Arc<OffsetTable>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(OffsetTable::default())));
+
+/// Every offset and signature the stable readers use.
+///
+/// Defaults to the values compiled into the crate (`COMMON_OFFSET`,
+/// `BEATMAP_OFFSET`, ..., `SIGNATURES`). With the `serde` feature a table can
+/// be loaded from a TOML or JSON file, any field left out keeps its built-in
+/// value so a patch only has to list what moved:
+///
+/// ```toml
+/// [gameplay]
+/// combo = 0x98
+///
+/// [signatures]
+/// status_sig = "48 83 F8 04 73 1E"
+/// ```
+///
+/// Readers always use the table installed with [`set_offsets`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct OffsetTable {
+    pub common: CommonOffset,
+    pub beatmap: BeatmapOffset,
+    pub gameplay: GameplayOffset,
+    pub result_screen: ResultScreenOffset,
+    pub user_profile: UserProfileOffset,
+    pub overlay: KeyOverlayOffset,
+    pub signatures: SignatureBase,
+}
+
+/// The table readers currently use
+pub fn offsets() -> Arc<OffsetTable> {
+    CURRENT.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Validates `table` and makes every reader use it from the next read on.
+///
+/// Signatures that were already resolved are kept, attach again (or call
+/// `StaticAddresses::retry_missing`) to scan for changed signatures.
+pub fn set_offsets(table: OffsetTable) -> Result<(), Error> {
+    table.validate()?;
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(table);
+    tracing::info!("offset table replaced");
+    Ok(())
+}
+
+/// Goes back to the built-in offsets
+pub fn reset_offsets() {
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(OffsetTable::default());
+}
+
+macro_rules! check_offsets {
+    ($errors:ident, $prefix:literal, $table:expr, [$($field:ident).+ $(, $($rest:ident).+)* $(,)?]) => {
+        check_offsets!(@one $errors, $prefix, $table, $($field).+);
+        $(check_offsets!(@one $errors, $prefix, $table, $($rest).+);)*
+    };
+    (@one $errors:ident, $prefix:literal, $table:expr, $($field:ident).+) => {
+        let value = $table.$($field).+;
+        if !(-MAX_OFFSET..=MAX_OFFSET).contains(&value) {
+            $errors.push(format!(
+                "{}.{} = {value:#x} is out of range",
+                $prefix,
+                stringify!($($field).+).replace(' ', ""),
+            ));
+        }
+    };
+}
+
+impl OffsetTable {
+    /// Checks every signature parses and every offset is in a sane range,
+    /// all problems are reported at once
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut errors = Vec::new();
+
+        for (name, sig) in self.signatures.entries() {
+            match Signature::from_str(sig) {
+                Ok(_) if sig.split(' ').all(|b| b == "??") => {
+                    errors.push(format!("signature {name} only has wildcards"))
+                }
+                Ok(_) => {}
+                Err(e) => errors.push(format!("signature {name} ({sig}): {e}")),
+            }
+        }
+
+        check_offsets!(
+            errors,
+            "common",
+            self.common,
+            [
+                settings_ptr,
+                settings_addr,
+                path,
+                status,
+                mods_ptr,
+                ig_time,
+                check_replay,
+                pause_ptr,
+                pause,
+                ruleset_ptr,
+                ruleset,
+            ]
+        );
+        check_offsets!(
+            errors,
+            "beatmap",
+            self.beatmap,
+            [
+                ptr,
+                metadata.author,
+                metadata.creator,
+                metadata.title_romanized,
+                metadata.title_original,
+                metadata.difficulty,
+                metadata.tags,
+                location.folder,
+                location.filename,
+                location.audio,
+                location.cover,
+                stats.ar,
+                stats.od,
+                stats.cs,
+                stats.hp,
+                stats.object_count,
+                stats.total_length,
+                stats.drain_time,
+                stats.star_rating,
+                stats.slider_count,
+                technical.md5,
+                technical.id,
+                technical.set_id,
+                technical.mode,
+                technical.ranked_status,
+            ]
+        );
+        check_offsets!(
+            errors,
+            "gameplay",
+            self.gameplay,
+            [
+                ptr, addr, base, score_base, ruleset, hp_base, score, mods, mods_xor, mods_xor2,
+                combo, max_combo, hp, username, retries, hits._300, hits._100, hits._50,
+                hits._miss, hits._geki, hits._katu,
+            ]
+        );
+        check_offsets!(
+            errors,
+            "result_screen",
+            self.result_screen,
+            [
+                ptr, addr, base, username, score, max_combo, mode, mods, mods_xor, mods_xor2, date,
+                hits._300, hits._100, hits._50, hits._miss, hits._geki, hits._katu,
+            ]
+        );
+        check_offsets!(
+            errors,
+            "user_profile",
+            self.user_profile,
+            [
+                ptr,
+                id,
+                username,
+                pp,
+                rankedscore,
+                level,
+                playcount,
+                rank,
+                playmode,
+                accuracy,
+                country_code,
+                bancho_status,
+            ]
+        );
+        check_offsets!(
+            errors,
+            "overlay",
+            self.overlay,
+            [
+                ptr,
+                addr,
+                base,
+                container,
+                array,
+                size,
+                pressed,
+                count,
+                keys.key_1,
+                keys.key_2,
+                keys.mouse_1,
+                keys.mouse_2,
+            ]
+        );
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Parse(format!(
+                "Invalid offset table: {}",
+                errors.join(", ")
+            )))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl OffsetTable {
+    pub fn from_toml(content: &str) -> Result<Self, Error> {
+        let table: Self = toml::from_str(content).map_err(|e| Error::Parse(e.to_string()))?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, Error> {
+        let table: Self = serde_json::from_str(content).map_err(|e| Error::Parse(e.to_string()))?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Loads a `.toml` or `.json` file, picked by extension
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml(&content),
+            Some("json") => Self::from_json(&content),
+            _ => Err(Error::Unsupported(format!(
+                "Offset table {} must be a .toml or .json file",
+                path.display()
+            ))),
+        }
+    }
+
+    pub fn to_toml(&self) -> Result<String, Error> {
+        toml::to_string_pretty(self).map_err(|e| Error::Parse(e.to_string()))
+    }
+}
+
+/// An [`OffsetTable`] together with the osu! builds it applies to
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OffsetProfile {
+    pub name: String,
+    /// First build using this layout, `None` for anything older
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min: Option<OsuVersion>,
+    /// Last build known to use this layout, `None` when there is no known end
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max: Option<OsuVersion>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub offsets: OffsetTable,
+}
+
+impl OffsetProfile {
+    pub fn supports(&self, version: &OsuVersion) -> bool {
+        self.min.as_ref().is_none_or(|min| version >= min)
+            && self.max.as_ref().is_none_or(|max| version <= max)
+    }
+}
+
+/// Offset tables keyed by osu! build, see [`OffsetProfiles::select`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OffsetProfiles {
+    #[cfg_attr(feature = "serde", serde(rename = "profile", default))]
+    profiles: Vec<OffsetProfile>,
+}
+
+impl Default for OffsetProfiles {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl OffsetProfiles {
+    /// The compiled-in offsets for every build, verified up to
+    /// [`LATEST_KNOWN_BUILD`].
+    ///
+    /// Offsets rarely move between builds so newer ones are not refused, a
+    /// profile with a later `min` can be pushed once they do.
+    pub fn builtin() -> Self {
+        Self {
+            profiles: vec![OffsetProfile {
+                name: "builtin".to_string(),
+                min: None,
+                max: None,
+                offsets: OffsetTable::default(),
+            }],
+        }
+    }
+
+    pub fn profiles(&self) -> &[OffsetProfile] {
+        &self.profiles
+    }
+
+    /// Adds a profile, it wins over every profile added before it
+    pub fn push(&mut self, profile: OffsetProfile) -> Result<(), Error> {
+        profile
+            .offsets
+            .validate()
+            .map_err(|e| Error::Parse(format!("Offset profile {}: {e}", profile.name)))?;
+        self.profiles.push(profile);
+        Ok(())
+    }
+
+    /// Most recently added profile covering `version`.
+    ///
+    /// Logs a warning when `version` is past both [`LATEST_KNOWN_BUILD`] and
+    /// the bounds of the profile, its offsets were never checked against it.
+    ///
+    /// Fails with `Error::Unsupported` when no profile covers `version`, which
+    /// only happens when every profile has a `max` (the built-in one has none).
+    pub fn select(&self, version: &OsuVersion) -> Result<&OffsetProfile, Error> {
+        if let Some(profile) = self.profiles.iter().rev().find(|p| p.supports(version)) {
+            let latest = LATEST_KNOWN_BUILD;
+            let verified = [profile.min.as_ref(), profile.max.as_ref()]
+                .into_iter()
+                .flatten()
+                .fold(&latest, |verified, bound| verified.max(bound));
+            if version > verified {
+                tracing::warn!(
+                    %version,
+                    %verified,
+                    profile = profile.name,
+                    "osu! build is newer than the offsets were verified against, values may be wrong"
+                );
+            }
+            return Ok(profile);
+        }
+
+        let newest = self.profiles.iter().filter_map(|p| p.max.as_ref()).max();
+        match newest {
+            Some(newest) if version > newest => Err(Error::Unsupported(format!(
+                "osu! {version} is newer than any known offset profile (latest is {newest})"
+            ))),
+            _ => Err(Error::Unsupported(format!(
+                "No offset profile covers osu! {version}"
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl OffsetProfiles {
+    /// Built-in profile followed by every `[[profile]]` of a TOML or JSON
+    /// file, picked by extension
+    ///
+    /// ```toml
+    /// [[profile]]
+    /// name = "2025 layout"
+    /// min = "b20250703.1"
+    ///
+    /// [profile.offsets.gameplay]
+    /// combo = 0x98
+    /// ```
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let file: Self = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&content).map_err(|e| Error::Parse(e.to_string()))?,
+            Some("json") => {
+                serde_json::from_str(&content).map_err(|e| Error::Parse(e.to_string()))?
+            }
+            _ => {
+                return Err(Error::Unsupported(format!(
+                    "Offset profiles {} must be a .toml or .json file",
+                    path.display()
+                )))
+            }
+        };
+
+        let mut profiles = Self::builtin();
+        for profile in file.profiles {
+            profiles.push(profile)?;
+        }
+        Ok(profiles)
+    }
+}
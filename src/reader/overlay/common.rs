@@ -1,9 +1,11 @@
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Key {
     pub pressed: bool,
     pub count: i32,
 }
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyOverlay {
     pub key_1: Key,
     pub key_2: Key,
@@ -1,2 +1,29 @@
 pub mod common;
 pub mod stable;
+
+use crate::impl_osu_accessor;
+use crate::memory::MemorySource;
+use crate::reader::common::OsuClientKind;
+use crate::reader::overlay::common::KeyOverlay;
+use crate::reader::structs::{State, StateHandle};
+use crate::Error;
+use rosu_mem::process::Process;
+
+pub struct OverlayReader<'a, P: MemorySource = Process, S: StateHandle = &'a mut State> {
+    pub process: &'a P,
+    pub state: S,
+    pub osu_type: OsuClientKind,
+}
+
+impl<'a, P: MemorySource, S: StateHandle> OverlayReader<'a, P, S> {
+    pub fn new(p: &'a P, state: S, osu_type: OsuClientKind) -> Self {
+        Self {
+            process: p,
+            state,
+            osu_type,
+        }
+    }
+    impl_osu_accessor! {
+        fn key_overlay() -> KeyOverlay => stable::memory::key_overlay_std,
+    }
+}
@@ -1 +1,63 @@
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct KeyOverlayOffset {
+    pub ptr: i32,
+    pub addr: i32,
+    pub base: i32,
+    pub container: i32,
+    pub array: i32,
+    pub size: i32,
+    pub pressed: i32,
+    pub count: i32,
+    pub keys: KeyOverlayKeysOffset,
+}
 
+pub const KEY_OVERLAY_OFFSET: KeyOverlayOffset = KeyOverlayOffset {
+    ptr: 0xb,
+    addr: 0x4,
+    base: 0xb0,
+    container: 0x10,
+    array: 0x4,
+    size: 0x4,
+    pressed: 0x1C,
+    count: 0x14,
+    keys: KEY_OVERLAY_KEYS_OFFSET,
+};
+
+/// Slots of the key array, each one points to a key holding `pressed` and
+/// `count`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct KeyOverlayKeysOffset {
+    pub key_1: i32,
+    pub key_2: i32,
+    pub mouse_1: i32,
+    pub mouse_2: i32,
+}
+
+pub const KEY_OVERLAY_KEYS_OFFSET: KeyOverlayKeysOffset = KeyOverlayKeysOffset {
+    key_1: 0x8,
+    key_2: 0xc,
+    mouse_1: 0x10,
+    mouse_2: 0x14,
+};
+
+impl Default for KeyOverlayOffset {
+    fn default() -> Self {
+        KEY_OVERLAY_OFFSET
+    }
+}
+
+impl Default for KeyOverlayKeysOffset {
+    fn default() -> Self {
+        KEY_OVERLAY_KEYS_OFFSET
+    }
+}
@@ -0,0 +1,331 @@
+use std::path::{Path, PathBuf};
+
+use crate::memory::MemorySource;
+use crate::reader::beatmap;
+use crate::reader::beatmap::stable::file::path as beatmap_path;
+use crate::reader::common::GameMode;
+use crate::reader::gameplay::common::GameplayInfo;
+use crate::reader::gameplay::stable::memory::info as gameplay_info;
+use crate::reader::structs::{Hit, State};
+use crate::Error;
+use rosu_mods::GameModsLegacy;
+use rosu_pp::any::{DifficultyAttributes, PerformanceAttributes, ScoreState};
+use rosu_pp::model::mode::GameMode as PpMode;
+use rosu_pp::{Beatmap, Difficulty, GradualPerformance, Performance};
+
+/// pp of the play in progress
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LivePpInfo {
+    /// pp of the objects played so far
+    pub current: f64,
+    /// pp if every remaining object is hit and misses are ignored
+    pub if_fc: f64,
+    /// pp of an SS with the same mods
+    pub max: f64,
+    /// Star rating of the objects played so far
+    pub current_stars: f64,
+}
+
+/// Live pp during gameplay, built on rosu-pp's gradual performance.
+///
+/// The parsed beatmap and its difficulty attributes are kept until the beatmap,
+/// the mode played or the mods change, and every update only processes the
+/// objects judged since the previous one. Converts are calculated on the
+/// beatmap converted to the mode being played. Retries are detected from `retries`, `ig_time`
+/// going back or fewer judged objects and start the calculation over.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rosu_memory_lib::init_loop;
+/// use rosu_memory_lib::reader::pp::LivePp;
+///
+/// let (mut state, process) = init_loop(500)?;
+/// let mut live = LivePp::new();
+/// loop {
+///     if let Ok(pp) = live.refresh(&process, &mut state) {
+///         println!("{:.2}pp (FC: {:.2}pp, SS: {:.2}pp)", pp.current, pp.if_fc, pp.max);
+///     }
+///     std::thread::sleep(std::time::Duration::from_millis(100));
+/// }
+/// # Ok::<(), rosu_memory_lib::Error>(())
+/// ```
+#[derive(Default)]
+pub struct LivePp {
+    path: PathBuf,
+    map: Option<Beatmap>,
+    /// `map` converted to `mode`
+    converted: Option<Beatmap>,
+    mode: GameMode,
+    mods: GameModsLegacy,
+    attributes: Option<DifficultyAttributes>,
+    max: f64,
+    gradual: Option<GradualPerformance>,
+    passed: usize,
+    current: Option<PerformanceAttributes>,
+    ig_time: i32,
+    retries: i32,
+}
+
+impl LivePp {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Switches to the beatmap at `path`, does nothing if it is already loaded
+    pub fn set_beatmap(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        if self.map.is_some() && self.path == path {
+            return Ok(());
+        }
+
+        let map = Beatmap::from_path(path)?;
+        map.check_suspicion()
+            .map_err(|e| Error::Parse(format!("Suspicious beatmap {}: {e:?}", path.display())))?;
+        self.path = path.to_path_buf();
+        self.map = Some(map);
+        self.converted = None;
+        self.attributes = None;
+        self.reset();
+        Ok(())
+    }
+
+    /// Starts the current play over, done automatically on retries
+    pub fn reset(&mut self) {
+        self.gradual = None;
+        self.passed = 0;
+        self.current = None;
+        self.ig_time = 0;
+    }
+
+    /// Reads the current beatmap and play, then [`LivePp::update`]s
+    pub fn refresh<P: MemorySource>(
+        &mut self,
+        p: &P,
+        state: &mut State,
+    ) -> Result<LivePpInfo, Error> {
+        let gameplay = gameplay_info(p, state)?;
+        self.set_beatmap(beatmap_path(p, state)?)?;
+        self.update(&gameplay)
+    }
+
+    /// Feeds the objects judged since the last update
+    pub fn update(&mut self, gameplay: &GameplayInfo) -> Result<LivePpInfo, Error> {
+        let map = self
+            .map
+            .take()
+            .ok_or_else(|| Error::NotAvailable("No beatmap loaded".to_string()))?;
+        let info = self.update_with(&map, gameplay);
+        self.map = Some(map);
+        info
+    }
+
+    fn update_with(&mut self, map: &Beatmap, gameplay: &GameplayInfo) -> Result<LivePpInfo, Error> {
+        let passed = passed_objects(&gameplay.mode, &gameplay.hits) as usize;
+
+        if self.attributes.is_none() || (self.mode, self.mods) != (gameplay.mode, gameplay.mods) {
+            let converted = convert(map, &gameplay.mode, gameplay.mods)?;
+            self.mode = gameplay.mode;
+            self.mods = gameplay.mods;
+            let attributes = difficulty(self.mods).calculate(&converted);
+            self.max = Performance::new(attributes.clone())
+                .mods(self.mods)
+                .lazer(false)
+                .calculate()
+                .pp();
+            self.attributes = Some(attributes);
+            self.converted = Some(converted);
+            self.reset();
+        }
+        if gameplay.retries != self.retries
+            || gameplay.ig_time < self.ig_time
+            || passed < self.passed
+        {
+            self.retries = gameplay.retries;
+            self.reset();
+        }
+        self.ig_time = gameplay.ig_time;
+
+        let converted = self.converted.as_ref().unwrap_or(map);
+        let gradual = self
+            .gradual
+            .get_or_insert_with(|| difficulty(self.mods).gradual_performance(converted));
+        if passed > self.passed {
+            self.current = gradual.nth(score_state(gameplay), passed - self.passed - 1);
+            self.passed = passed;
+        }
+
+        Ok(LivePpInfo {
+            current: self.current.as_ref().map_or(0.0, |attrs| attrs.pp()),
+            if_fc: self.if_fc(&gameplay.hits),
+            max: self.max,
+            current_stars: self.current.as_ref().map_or(0.0, |attrs| attrs.stars()),
+        })
+    }
+
+    /// Misses count as perfect hits, everything not judged yet as well
+    fn if_fc(&self, hits: &Hit) -> f64 {
+        let Some(attributes) = &self.attributes else {
+            return 0.0;
+        };
+        Performance::new(attributes.clone())
+            .mods(self.mods)
+            .lazer(false)
+            .state(fc_state(attributes, &self.mode, hits))
+            .calculate()
+            .pp()
+    }
+}
+
+/// `map` as played in `mode`, only osu!standard beatmaps can be converted
+pub(crate) fn convert(
+    map: &Beatmap,
+    mode: &GameMode,
+    mods: GameModsLegacy,
+) -> Result<Beatmap, Error> {
+    let mut converted = map.clone();
+    if let Some(ruleset) = ruleset(mode) {
+        converted
+            .convert_mut(ruleset, &mods.into())
+            .map_err(|e| Error::Parse(format!("Cannot convert beatmap to {mode:?}: {e}")))?;
+    }
+    Ok(converted)
+}
+
+/// Full combo state for `hits` so far: misses and every object not judged yet
+/// become perfect hits (geki in osu!mania, 300 otherwise)
+fn fc_state(attributes: &DifficultyAttributes, mode: &GameMode, hits: &Hit) -> ScoreState {
+    let count = |n: i16| n.max(0) as u32;
+    let mut state = hit_state(hits, 0);
+    state.max_combo = attributes.max_combo();
+    let remaining = object_count(attributes).saturating_sub(passed_objects(mode, hits));
+
+    match attributes {
+        // misses can be fruits or droplets, every one of them is caught and
+        // only the tiny droplets already missed stay missed
+        DifficultyAttributes::Catch(attrs) => {
+            state.n300 = attrs.n_fruits;
+            state.n100 = attrs.n_droplets;
+            state.n50 = attrs.n_tiny_droplets.saturating_sub(count(hits._katu));
+        }
+        DifficultyAttributes::Mania(_) => state.n_geki += state.misses + remaining,
+        _ => state.n300 += state.misses + remaining,
+    }
+    state.misses = 0;
+    state
+}
+
+fn difficulty(mods: GameModsLegacy) -> Difficulty {
+    Difficulty::new().mods(mods).lazer(false)
+}
+
+/// pp of a finished play, and whether every object of the beatmap was
+/// judged. The beatmap is only parsed again for fails and converts, the
+/// difficulty attributes come from [`difficulty`](crate::reader::beatmap::difficulty)
+pub(crate) fn score_pp(
+    md5: &str,
+    path: &Path,
+    mode: &GameMode,
+    mods: GameModsLegacy,
+    hits: &Hit,
+    max_combo: i16,
+) -> Result<(f64, bool), Error> {
+    let mut attributes = beatmap::difficulty::attributes(md5, path, mods)?;
+    let mut map = None;
+    if let Some(ruleset) = ruleset(mode).filter(|ruleset| *ruleset != attributes_mode(&attributes))
+    {
+        let mut converted = Beatmap::from_path(path)?;
+        converted
+            .convert_mut(ruleset, &mods.into())
+            .map_err(|e| Error::Parse(format!("Cannot convert {}: {e}", path.display())))?;
+        attributes = difficulty(mods).calculate(&converted);
+        map = Some(converted);
+    }
+
+    let judged = passed_objects(mode, hits);
+    let passed = judged >= object_count(&attributes);
+    let state = hit_state(hits, max_combo);
+    let pp = if passed {
+        Performance::new(attributes)
+            .mods(mods)
+            .lazer(false)
+            .state(state)
+            .calculate()
+            .pp()
+    } else {
+        let map = match map {
+            Some(map) => map,
+            None => Beatmap::from_path(path)?,
+        };
+        Performance::new(&map)
+            .mode_or_ignore(attributes_mode(&attributes))
+            .mods(mods)
+            .lazer(false)
+            .passed_objects(judged)
+            .state(state)
+            .calculate()
+            .pp()
+    };
+    Ok((pp, passed))
+}
+
+pub(crate) fn ruleset(mode: &GameMode) -> Option<PpMode> {
+    match mode {
+        GameMode::Osu => Some(PpMode::Osu),
+        GameMode::Taiko => Some(PpMode::Taiko),
+        GameMode::Catch => Some(PpMode::Catch),
+        GameMode::Mania => Some(PpMode::Mania),
+        GameMode::Unknown => None,
+    }
+}
+
+fn attributes_mode(attributes: &DifficultyAttributes) -> PpMode {
+    match attributes {
+        DifficultyAttributes::Osu(_) => PpMode::Osu,
+        DifficultyAttributes::Taiko(_) => PpMode::Taiko,
+        DifficultyAttributes::Catch(_) => PpMode::Catch,
+        DifficultyAttributes::Mania(_) => PpMode::Mania,
+    }
+}
+
+/// Objects of the beatmap, counted like [`passed_objects`]
+fn object_count(attributes: &DifficultyAttributes) -> u32 {
+    match attributes {
+        DifficultyAttributes::Osu(attrs) => attrs.n_objects(),
+        // Every circle gives one combo
+        DifficultyAttributes::Taiko(attrs) => attrs.max_combo,
+        DifficultyAttributes::Catch(attrs) => attrs.n_fruits + attrs.n_droplets,
+        DifficultyAttributes::Mania(attrs) => attrs.n_objects,
+    }
+}
+
+fn score_state(gameplay: &GameplayInfo) -> ScoreState {
+    hit_state(&gameplay.hits, gameplay.max_combo)
+}
+
+fn hit_state(hits: &Hit, max_combo: i16) -> ScoreState {
+    let count = |n: i16| n.max(0) as u32;
+    ScoreState {
+        max_combo: count(max_combo),
+        n_geki: count(hits._geki),
+        n_katu: count(hits._katu),
+        n300: count(hits._300),
+        n100: count(hits._100),
+        n50: count(hits._50),
+        misses: count(hits._miss),
+        ..Default::default()
+    }
+}
+
+/// Hit objects judged so far, as counted by rosu-pp
+pub(crate) fn passed_objects(mode: &GameMode, hits: &Hit) -> u32 {
+    let count = |n: i16| n.max(0) as u32;
+    let common = count(hits._300) + count(hits._100) + count(hits._miss);
+    match mode {
+        GameMode::Mania => common + count(hits._geki) + count(hits._katu) + count(hits._50),
+        // Tiny droplets (50) don't count as objects in catch
+        GameMode::Taiko | GameMode::Catch => common,
+        _ => common + count(hits._50),
+    }
+}
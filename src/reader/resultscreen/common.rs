@@ -1,12 +1,33 @@
 use crate::reader::common::GameMode;
+use crate::reader::grade::Grade;
 use crate::reader::structs::Hit;
+use rosu_mods::GameModsLegacy;
 
-#[derive(Debug, Default)]
+/// Score shown on the result screen.
+///
+/// Replay metadata (online score id, replay checksum, whether a replay file
+/// exists) isn't part of it, osu! keeps it outside of the score object read
+/// here and there are no verified offsets for it.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResultScreenInfo {
     pub username: String,
     pub mode: GameMode,
+    pub mods: GameModsLegacy,
     pub max_combo: i16,
     pub score: i32,
     pub hits: Hit,
     pub accuracy: f64,
+    /// `F` when the play didn't reach the end of the beatmap
+    pub grade: Grade,
+    /// When the score was set, in milliseconds since the unix epoch (local time)
+    pub date: i64,
+    /// Empty when the selected beatmap can't be read
+    pub beatmap_md5: String,
+    /// Whether every object of the beatmap was judged, assumed when the
+    /// beatmap md5 or file can't be read
+    pub passed: bool,
+    /// Computed with rosu-pp from the hits, `None` without the beatmap md5 or
+    /// file
+    pub pp: Option<f64>,
 }
@@ -1,21 +1,24 @@
 pub mod common;
 pub mod stable;
 use crate::impl_osu_accessor;
+use crate::memory::MemorySource;
 use crate::reader::common::GameMode;
 use crate::reader::common::OsuClientKind;
+use crate::reader::grade::Grade;
 use crate::reader::resultscreen::common::ResultScreenInfo;
 use crate::reader::structs::Hit;
-use crate::reader::structs::State;
+use crate::reader::structs::{State, StateHandle};
 use crate::Error;
 use rosu_mem::process::Process;
-pub struct ResultScreenReader<'a> {
-    pub process: &'a Process,
-    pub state: &'a mut State,
+use rosu_mods::GameModsLegacy;
+pub struct ResultScreenReader<'a, P: MemorySource = Process, S: StateHandle = &'a mut State> {
+    pub process: &'a P,
+    pub state: S,
     pub osu_type: OsuClientKind,
 }
 
-impl<'a> ResultScreenReader<'a> {
-    pub fn new(p: &'a Process, state: &'a mut State, osu_type: OsuClientKind) -> Self {
+impl<'a, P: MemorySource, S: StateHandle> ResultScreenReader<'a, P, S> {
+    pub fn new(p: &'a P, state: S, osu_type: OsuClientKind) -> Self {
         Self {
             process: p,
             state,
@@ -26,6 +29,7 @@ impl<'a> ResultScreenReader<'a> {
         fn username() -> String => stable::memory::username,
         fn score() -> i32 => stable::memory::score,
         fn mode() -> GameMode => stable::memory::mode,
+        fn mods() -> GameModsLegacy => stable::memory::mods,
         fn max_combo() -> i16 => stable::memory::max_combo,
         fn hits() -> Hit => stable::memory::hits,
         fn hits_300() -> i16 => stable::memory::hits_300,
@@ -35,6 +39,11 @@ impl<'a> ResultScreenReader<'a> {
         fn hits_geki() -> i16 => stable::memory::hits_geki,
         fn hits_katu() -> i16 => stable::memory::hits_katu,
         fn accuracy() -> f64 => stable::memory::accuracy,
+        fn grade() -> Grade => stable::memory::grade,
+        fn date() -> i64 => stable::memory::date,
+        fn beatmap_md5() -> String => stable::memory::beatmap_md5,
+        fn passed() -> bool => stable::memory::passed,
+        fn pp() -> f64 => stable::memory::pp,
         fn info() -> ResultScreenInfo => stable::memory::info,
     }
 }
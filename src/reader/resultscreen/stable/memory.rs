@@ -1,74 +1,202 @@
 use crate::generate_offset_getter;
+use crate::memory::MemorySource;
+use crate::reader::beatmap;
 use crate::reader::common::stable::memory::check_game_state;
 use crate::reader::common::GameMode;
 use crate::reader::common::GameState;
-use crate::reader::helpers::{calculate_accuracy, read_i16, read_i32, read_string};
+use crate::reader::grade::{self, Grade};
+use crate::reader::helpers::{
+    calculate_accuracy, read_hits, read_i16, read_i32, read_i64, read_string, read_u64,
+};
+use crate::reader::offsets::offsets;
+use crate::reader::pp::score_pp;
 use crate::reader::resultscreen::common::ResultScreenInfo;
-use crate::reader::resultscreen::stable::offset::RESULT_SCREEN_OFFSET;
-use crate::reader::structs::{Hit, State};
+use crate::reader::structs::{Hit, SignatureName, State};
 use crate::Error;
-use rosu_mem::process::{Process, ProcessTraits};
+use rosu_mods::GameModsLegacy;
 
-pub fn result_screen_ptr(p: &Process, state: &mut State) -> Result<i32, Error> {
+pub fn result_screen_ptr<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
     if check_game_state(p, state, GameState::ResultScreen)? {
-        Ok(p.read_i32(state.addresses.rulesets - RESULT_SCREEN_OFFSET.ptr)?)
+        result_screen_ptr_unchecked(p, state)
     } else {
         Err(Error::NotAvailable("Not in ResultScreen".to_string()))
     }
 }
 
-pub fn hits(p: &Process, state: &mut State) -> Result<Hit, Error> {
+/// Same as `result_screen_ptr` for callers that already checked the game state
+pub(crate) fn result_screen_ptr_unchecked<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+) -> Result<i32, Error> {
+    p.read_i32(state.address(p, SignatureName::Rulesets)? - offsets().result_screen.ptr)
+}
+
+pub fn hits<P: MemorySource>(p: &P, state: &mut State) -> Result<Hit, Error> {
     let score_base = result_screen_base(p, state)?;
-    // Read all hits data in one memory operation
-    let mut hits_buffer = [0u8; size_of::<i16>() * 6];
-    p.read(
-        score_base + RESULT_SCREEN_OFFSET.hits._100,
-        size_of::<i16>() * 6,
-        &mut hits_buffer,
-    )?;
-
-    // Safety: unwrap here because buffer is already initialized and filled
-    // with zeros, the worst case scenario is hits going to be zeros
-    Ok(Hit {
-        _100: i16::from_le_bytes(hits_buffer[0..2].try_into().unwrap()),
-        _300: i16::from_le_bytes(hits_buffer[2..4].try_into().unwrap()),
-        _50: i16::from_le_bytes(hits_buffer[4..6].try_into().unwrap()),
-        _geki: i16::from_le_bytes(hits_buffer[6..8].try_into().unwrap()),
-        _katu: i16::from_le_bytes(hits_buffer[8..10].try_into().unwrap()),
-        _miss: i16::from_le_bytes(hits_buffer[10..12].try_into().unwrap()),
+    read_hits(p, score_base + offsets().result_screen.hits._100)
+}
+
+pub fn mods<P: MemorySource>(p: &P, state: &mut State) -> Result<GameModsLegacy, Error> {
+    let mods_xor1 = mods_xor1(p, state)?;
+    let mods_xor2 = mods_xor2(p, state)?;
+    Ok(GameModsLegacy::from_bits((mods_xor1 ^ mods_xor2) as u32))
+}
+
+/// When the score was set, in milliseconds since the unix epoch. osu! keeps
+/// the local time, not UTC
+pub fn date<P: MemorySource>(p: &P, state: &mut State) -> Result<i64, Error> {
+    Ok(unix_millis(read_i64(
+        p,
+        state,
+        offsets().result_screen.date,
+        result_screen_base,
+    )?))
+}
+
+/// md5 of the beatmap the score was set on, which is still the selected one
+pub fn beatmap_md5<P: MemorySource>(p: &P, state: &mut State) -> Result<String, Error> {
+    beatmap::stable::memory::md5(p, state)
+}
+
+/// pp of the score, see [`ResultScreenInfo::pp`]
+pub fn pp<P: MemorySource>(p: &P, state: &mut State) -> Result<f64, Error> {
+    score_pp_of(p, state)?
+        .0
+        .ok_or_else(|| Error::NotAvailable("No beatmap file to calculate pp".to_string()))
+}
+
+/// Whether every object of the beatmap was judged, see [`ResultScreenInfo::passed`]
+pub fn passed<P: MemorySource>(p: &P, state: &mut State) -> Result<bool, Error> {
+    Ok(score_pp_of(p, state)?.1)
+}
+
+/// Grade of the score, `F` when it didn't pass, see [`ResultScreenInfo::passed`]
+pub fn grade<P: MemorySource>(p: &P, state: &mut State) -> Result<Grade, Error> {
+    if !passed(p, state)? {
+        return Ok(Grade::F);
+    }
+    Ok(grade::grade(
+        &mode(p, state)?,
+        &hits(p, state)?,
+        mods(p, state)?,
+    ))
+}
+
+fn score_pp_of<P: MemorySource>(p: &P, state: &mut State) -> Result<(Option<f64>, bool), Error> {
+    let (mode, mods) = (mode(p, state)?, mods(p, state)?);
+    let (hits, max_combo) = (hits(p, state)?, max_combo(p, state)?);
+    let md5 = selected_md5(p, state);
+    Ok(pp_and_passed(p, state, &md5, &mode, mods, &hits, max_combo))
+}
+
+/// md5 of the selected beatmap, empty when it can't be read
+fn selected_md5<P: MemorySource>(p: &P, state: &mut State) -> String {
+    beatmap_md5(p, state).unwrap_or_else(|e| {
+        tracing::debug!(error = %e, "no beatmap md5 for the result screen");
+        String::new()
     })
 }
 
-pub fn accuracy(p: &Process, state: &mut State) -> Result<f64, Error> {
+/// pp of the score and whether it passed. Without the beatmap md5 or file the
+/// pp is unknown and the score is taken as a pass, the result screen mostly
+/// shows finished plays
+fn pp_and_passed<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+    md5: &str,
+    mode: &GameMode,
+    mods: GameModsLegacy,
+    hits: &Hit,
+    max_combo: i16,
+) -> (Option<f64>, bool) {
+    let score_pp = if md5.is_empty() {
+        Err(Error::NotAvailable("No beatmap md5".to_string()))
+    } else {
+        beatmap::stable::file::path(p, state)
+            .and_then(|path| score_pp(md5, &path, mode, mods, hits, max_combo))
+    };
+    match score_pp {
+        Ok((pp, passed)) => (Some(pp), passed),
+        Err(e) => {
+            tracing::debug!(error = %e, "cannot calculate result screen pp");
+            (None, true)
+        }
+    }
+}
+
+/// .NET `DateTime` ticks (100ns since year 1, kind in the two high bits) to
+/// unix milliseconds
+fn unix_millis(date_data: i64) -> i64 {
+    const TICKS_MASK: i64 = 0x3FFF_FFFF_FFFF_FFFF;
+    const UNIX_EPOCH_TICKS: i64 = 621_355_968_000_000_000;
+    ((date_data & TICKS_MASK) - UNIX_EPOCH_TICKS) / 10_000
+}
+
+pub fn accuracy<P: MemorySource>(p: &P, state: &mut State) -> Result<f64, Error> {
     calculate_accuracy(&mode(p, state)?, &hits(p, state)?)
 }
 
 generate_offset_getter! {
-    result_screen_addr: i32 = read_i32(RESULT_SCREEN_OFFSET.addr, result_screen_ptr);
-    result_screen_base: i32 = read_i32(RESULT_SCREEN_OFFSET.base, result_screen_addr);
-    username: String = read_string(RESULT_SCREEN_OFFSET.username, result_screen_base);
-    score: i32 = read_i32(RESULT_SCREEN_OFFSET.score, result_screen_base);
-    max_combo: i16 = read_i16(RESULT_SCREEN_OFFSET.max_combo, result_screen_base);
-    mode: GameMode = read_i32(RESULT_SCREEN_OFFSET.mode, result_screen_base);
-    hits_300: i16 = read_i16(RESULT_SCREEN_OFFSET.hits._300, result_screen_base);
-    hits_100: i16 = read_i16(RESULT_SCREEN_OFFSET.hits._100, result_screen_base);
-    hits_50: i16 = read_i16(RESULT_SCREEN_OFFSET.hits._50, result_screen_base);
-    hits_miss: i16 = read_i16(RESULT_SCREEN_OFFSET.hits._miss, result_screen_base);
-    hits_geki: i16 = read_i16(RESULT_SCREEN_OFFSET.hits._geki, result_screen_base);
-    hits_katu: i16 = read_i16(RESULT_SCREEN_OFFSET.hits._katu, result_screen_base);
-}
-
-pub fn info(p: &Process, state: &mut State) -> Result<ResultScreenInfo, Error> {
-    let hits = hits(p, state)?;
-    let mode = mode(p, state)?;
-    let accuracy = calculate_accuracy(&mode, &hits)?;
+    result_screen_addr: i32 = read_i32(offsets().result_screen.addr, result_screen_ptr);
+    result_screen_base: i32 = read_i32(offsets().result_screen.base, result_screen_addr);
+    username: String = read_string(offsets().result_screen.username, result_screen_base);
+    score: i32 = read_i32(offsets().result_screen.score, result_screen_base);
+    max_combo: i16 = read_i16(offsets().result_screen.max_combo, result_screen_base);
+    mode: GameMode = read_i32(offsets().result_screen.mode, result_screen_base);
+    mods_xor_base: i32 = read_i32(offsets().result_screen.mods, result_screen_base);
+    mods_xor1: u64 = read_u64(offsets().result_screen.mods_xor, mods_xor_base);
+    mods_xor2: u64 = read_u64(offsets().result_screen.mods_xor2, mods_xor_base);
+    hits_300: i16 = read_i16(offsets().result_screen.hits._300, result_screen_base);
+    hits_100: i16 = read_i16(offsets().result_screen.hits._100, result_screen_base);
+    hits_50: i16 = read_i16(offsets().result_screen.hits._50, result_screen_base);
+    hits_miss: i16 = read_i16(offsets().result_screen.hits._miss, result_screen_base);
+    hits_geki: i16 = read_i16(offsets().result_screen.hits._geki, result_screen_base);
+    hits_katu: i16 = read_i16(offsets().result_screen.hits._katu, result_screen_base);
+}
+
+pub fn info<P: MemorySource>(p: &P, state: &mut State) -> Result<ResultScreenInfo, Error> {
     let base = result_screen_base(p, state)?;
+    info_at(p, state, base)
+}
+
+/// Result screen info starting from an already resolved `result_screen_base`,
+/// the beatmap md5 and file come from the selected beatmap
+pub(crate) fn info_at<P: MemorySource>(
+    p: &P,
+    state: &mut State,
+    base: i32,
+) -> Result<ResultScreenInfo, Error> {
+    let offsets = offsets();
+
+    let hits = read_hits(p, base + offsets.result_screen.hits._100)?;
+    let mode = GameMode::from(p.read_i32(base + offsets.result_screen.mode)?);
+    let mods_xor_base = p.read_i32(base + offsets.result_screen.mods)?;
+    let mods = GameModsLegacy::from_bits(
+        (p.read_u64(mods_xor_base + offsets.result_screen.mods_xor)?
+            ^ p.read_u64(mods_xor_base + offsets.result_screen.mods_xor2)?) as u32,
+    );
+    let accuracy = calculate_accuracy(&mode, &hits)?;
+    let max_combo = p.read_i16(base + offsets.result_screen.max_combo)?;
+
+    let beatmap_md5 = selected_md5(p, state);
+    let (pp, passed) = pp_and_passed(p, state, &beatmap_md5, &mode, mods, &hits, max_combo);
+    let grade = match passed {
+        true => grade::grade(&mode, &hits, mods),
+        false => Grade::F,
+    };
+
     Ok(ResultScreenInfo {
-        username: p.read_string(base + RESULT_SCREEN_OFFSET.username)?,
+        username: p.read_string(base + offsets.result_screen.username)?,
         mode,
-        max_combo: p.read_i16(base + RESULT_SCREEN_OFFSET.max_combo)?,
-        score: p.read_i32(base + RESULT_SCREEN_OFFSET.score)?,
+        mods,
+        max_combo,
+        score: p.read_i32(base + offsets.result_screen.score)?,
         hits,
         accuracy,
+        grade,
+        date: unix_millis(p.read_i64(base + offsets.result_screen.date)?),
+        beatmap_md5,
+        passed,
+        pp,
     })
 }
@@ -1,2 +1,2 @@
 pub mod memory;
-mod offset;
+pub mod offset;
@@ -1,3 +1,9 @@
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct ResultScreenOffset {
     pub ptr: i32,
     pub addr: i32,
@@ -6,10 +12,14 @@ pub struct ResultScreenOffset {
     pub score: i32,
     pub max_combo: i32,
     pub mode: i32,
+    pub mods: i32,
+    pub mods_xor: i32,
+    pub mods_xor2: i32,
+    pub date: i32,
     pub hits: ResultScreenHitsOffset,
 }
 
-pub(crate) const RESULT_SCREEN_OFFSET: ResultScreenOffset = ResultScreenOffset {
+pub const RESULT_SCREEN_OFFSET: ResultScreenOffset = ResultScreenOffset {
     ptr: 0xb,
     addr: 0x4,
     base: 0x38,
@@ -17,9 +27,19 @@ pub(crate) const RESULT_SCREEN_OFFSET: ResultScreenOffset = ResultScreenOffset {
     score: 0x78,
     max_combo: 0x68,
     mode: 0x64,
+    mods: 0x1C,
+    mods_xor: 0xc,
+    mods_xor2: 0x8,
+    date: 0xA0,
     hits: RESULT_SCREEN_HITS_OFFSET,
 };
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct ResultScreenHitsOffset {
     pub _300: i32,
     pub _100: i32,
@@ -29,7 +49,7 @@ pub struct ResultScreenHitsOffset {
     pub _katu: i32,
 }
 
-pub(crate) const RESULT_SCREEN_HITS_OFFSET: ResultScreenHitsOffset = ResultScreenHitsOffset {
+pub const RESULT_SCREEN_HITS_OFFSET: ResultScreenHitsOffset = ResultScreenHitsOffset {
     _100: 0x88,  // 136
     _300: 0x8A,  // 138
     _50: 0x8C,   // 140
@@ -37,3 +57,15 @@ pub(crate) const RESULT_SCREEN_HITS_OFFSET: ResultScreenHitsOffset = ResultScree
     _katu: 0x90, // 144
     _miss: 0x92, // 146
 };
+
+impl Default for ResultScreenOffset {
+    fn default() -> Self {
+        RESULT_SCREEN_OFFSET
+    }
+}
+
+impl Default for ResultScreenHitsOffset {
+    fn default() -> Self {
+        RESULT_SCREEN_HITS_OFFSET
+    }
+}
@@ -0,0 +1,123 @@
+use std::sync::Mutex;
+
+use crate::memory::MemorySource;
+use crate::reader::beatmap::BeatmapReader;
+use crate::reader::common::{CommonReader, OsuClientKind};
+use crate::reader::gameplay::GameplayReader;
+use crate::reader::overlay::OverlayReader;
+use crate::reader::resultscreen::ResultScreenReader;
+use crate::reader::snapshot::OsuSnapshot;
+use crate::reader::structs::State;
+use crate::reader::user::UserReader;
+use crate::reader::wait::Waiter;
+use crate::reader::{init_loop, init_loop_with};
+use crate::Error;
+use rosu_mem::process::Process;
+
+/// Owns the process and its `State` and hands out reader views.
+///
+/// Views only borrow the session, so any number of them can be alive at the
+/// same time, and the session itself can be moved to another thread.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rosu_memory_lib::reader::session::OsuSession;
+///
+/// let session = OsuSession::attach(500)?;
+/// let worker = std::thread::spawn(move || {
+///     let mut beatmap = session.beatmap();
+///     let mut gameplay = session.gameplay();
+///     println!("{:?} {:?}", beatmap.md5(), gameplay.combo());
+/// });
+/// worker.join().unwrap();
+/// # Ok::<(), rosu_memory_lib::Error>(())
+/// ```
+pub struct OsuSession<P: MemorySource = Process> {
+    process: P,
+    state: Mutex<State>,
+    osu_type: OsuClientKind,
+}
+
+impl OsuSession<Process> {
+    /// Waits for osu! like [`init_loop`] and wraps the result
+    pub fn attach(sleep_duration: u64) -> Result<Self, Error> {
+        let (state, process) = init_loop(sleep_duration)?;
+        Ok(Self::new(process, state))
+    }
+
+    /// Waits for osu! like [`init_loop_with`] and wraps the result
+    pub fn attach_with(waiter: &Waiter) -> Result<Self, Error> {
+        let (state, process) = init_loop_with(waiter)?;
+        Ok(Self::new(process, state))
+    }
+}
+
+impl<P: MemorySource> OsuSession<P> {
+    pub fn new(process: P, state: State) -> Self {
+        Self {
+            process,
+            state: Mutex::new(state),
+            osu_type: OsuClientKind::Stable,
+        }
+    }
+
+    pub fn process(&self) -> &P {
+        &self.process
+    }
+
+    /// Copy of the current `State`
+    pub fn state(&self) -> State {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn osu_type(&self) -> OsuClientKind {
+        self.osu_type
+    }
+
+    pub fn into_parts(self) -> (State, P) {
+        let state = self.state.into_inner().unwrap_or_else(|e| e.into_inner());
+        (state, self.process)
+    }
+
+    pub fn common(&self) -> CommonReader<'_, P, &Mutex<State>> {
+        CommonReader::new(&self.process, &self.state, self.osu_type)
+    }
+
+    pub fn beatmap(&self) -> BeatmapReader<'_, P, &Mutex<State>> {
+        BeatmapReader {
+            process: &self.process,
+            state: &self.state,
+            osu_type: self.osu_type,
+        }
+    }
+
+    pub fn gameplay(&self) -> GameplayReader<'_, P, &Mutex<State>> {
+        GameplayReader::new(&self.process, &self.state, self.osu_type)
+    }
+
+    pub fn result_screen(&self) -> ResultScreenReader<'_, P, &Mutex<State>> {
+        ResultScreenReader::new(&self.process, &self.state, self.osu_type)
+    }
+
+    pub fn user(&self) -> UserReader<'_, P, &Mutex<State>> {
+        UserReader::new(&self.process, &self.state, self.osu_type)
+    }
+
+    pub fn overlay(&self) -> OverlayReader<'_, P, &Mutex<State>> {
+        OverlayReader::new(&self.process, &self.state, self.osu_type)
+    }
+
+    /// Reads everything relevant for the current game state at once, see
+    /// [`OsuSnapshot::refresh`] to keep reusing the previous one
+    pub fn snapshot(&self) -> Result<OsuSnapshot, Error> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        OsuSnapshot::read(&self.process, &mut state)
+    }
+
+    /// Updates `snapshot` in place, see [`OsuSnapshot::refresh`]
+    pub fn refresh(&self, snapshot: &mut OsuSnapshot) -> Result<(), Error> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        snapshot.refresh(&self.process, &mut state)
+    }
+}
@@ -0,0 +1,162 @@
+use crate::memory::MemorySource;
+use crate::reader::beatmap::common::BeatmapInfo;
+use crate::reader::beatmap::stable::beatmap_addr_unchecked;
+use crate::reader::common::stable::memory::{game_state, game_time, menu_mods, menu_ruleset};
+use crate::reader::common::{GameMode, GameState};
+#[cfg(feature = "serde")]
+use crate::reader::diff::{apply, diff, PatchOp};
+use crate::reader::gameplay::common::GameplayInfo;
+use crate::reader::gameplay::stable::memory::rulesets_addr_unchecked;
+use crate::reader::offsets::offsets;
+use crate::reader::overlay::common::KeyOverlay;
+use crate::reader::overlay::stable::memory::key_overlay_std;
+use crate::reader::resultscreen::common::ResultScreenInfo;
+use crate::reader::resultscreen::stable::memory::result_screen_ptr_unchecked;
+use crate::reader::structs::State;
+use crate::reader::user::common::UserInfo;
+use crate::reader::user::stable::memory::info as user_info;
+use crate::reader::{beatmap, gameplay, resultscreen};
+use crate::Error;
+use rosu_mods::GameModsLegacy;
+
+/// Everything an overlay needs for one tick.
+///
+/// Sections that don't make sense in the current `game_state` (or that
+/// couldn't be read this tick) are `None`. The game state is read once and
+/// every section starts from its base pointer instead of going through the
+/// individual getters, which all re-check the game state.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rosu_memory_lib::init_loop;
+/// use rosu_memory_lib::reader::snapshot::OsuSnapshot;
+///
+/// let (mut state, process) = init_loop(500)?;
+/// let mut snapshot = OsuSnapshot::default();
+/// loop {
+///     snapshot.refresh(&process, &mut state)?;
+///     if let Some(gameplay) = &snapshot.gameplay {
+///         println!("{:?} {}x", snapshot.game_state, gameplay.combo);
+///     }
+///     std::thread::sleep(std::time::Duration::from_millis(16));
+/// }
+/// # Ok::<(), rosu_memory_lib::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OsuSnapshot {
+    pub game_state: GameState,
+    pub menu_mods: Option<GameModsLegacy>,
+    /// Mode picked in song select, can differ from the beatmap mode for converts
+    pub menu_ruleset: Option<GameMode>,
+    /// Audio position in milliseconds, also moves in song select
+    pub game_time: Option<i32>,
+    /// Song select, editor, playing and result screen
+    pub beatmap: Option<BeatmapInfo>,
+    /// Playing only
+    pub gameplay: Option<GameplayInfo>,
+    /// Playing osu!standard only
+    pub key_overlay: Option<KeyOverlay>,
+    /// Result screen only
+    pub result_screen: Option<ResultScreenInfo>,
+    pub user: Option<UserInfo>,
+}
+
+impl OsuSnapshot {
+    /// Reads a fresh snapshot
+    pub fn read<P: MemorySource>(p: &P, state: &mut State) -> Result<Self, Error> {
+        let mut snapshot = Self::default();
+        snapshot.refresh(p, state)?;
+        Ok(snapshot)
+    }
+
+    /// Updates the snapshot in place.
+    ///
+    /// Beatmap info is only read again when the beatmap md5 changed since it
+    /// involves parsing the beatmap file, the star rating still follows the
+    /// menu mods and ruleset. Only a failure to read the game state is an
+    /// error.
+    pub fn refresh<P: MemorySource>(&mut self, p: &P, state: &mut State) -> Result<(), Error> {
+        self.game_state = game_state(p, state)?;
+        self.menu_mods = menu_mods(p, state).ok();
+        self.menu_ruleset = menu_ruleset(p, state).ok();
+        self.game_time = game_time(p, state).ok();
+        self.user = user_info(p, state).ok();
+
+        let playing = self.game_state == GameState::Playing;
+        let result_screen = self.game_state == GameState::ResultScreen;
+        let has_beatmap = matches!(
+            self.game_state,
+            GameState::SongSelect
+                | GameState::Editor
+                | GameState::Playing
+                | GameState::ResultScreen
+        );
+
+        self.beatmap = if has_beatmap {
+            self.read_beatmap(p, state)
+        } else {
+            None
+        };
+        self.gameplay = playing.then(|| read_gameplay(p, state)).flatten();
+        self.key_overlay = playing.then(|| key_overlay_std(p, state).ok()).flatten();
+        self.result_screen = result_screen
+            .then(|| read_result_screen(p, state))
+            .flatten();
+        Ok(())
+    }
+
+    /// Patch turning `self` into `new`, see [`crate::reader::diff`]
+    #[cfg(feature = "serde")]
+    pub fn diff(&self, new: &OsuSnapshot) -> Result<Vec<PatchOp>, Error> {
+        Ok(diff(&to_value(self)?, &to_value(new)?))
+    }
+
+    /// Applies a patch produced by [`OsuSnapshot::diff`] on the sender's copy
+    /// of `self`
+    #[cfg(feature = "serde")]
+    pub fn apply(&mut self, patch: &[PatchOp]) -> Result<(), Error> {
+        let mut value = to_value(&*self)?;
+        apply(&mut value, patch)?;
+        *self = serde_json::from_value(value).map_err(|e| Error::Parse(e.to_string()))?;
+        Ok(())
+    }
+
+    fn read_beatmap<P: MemorySource>(&mut self, p: &P, state: &mut State) -> Option<BeatmapInfo> {
+        let addr = beatmap_addr_unchecked(p, state).ok()?;
+        let current = p.read_string(addr + offsets().beatmap.technical.md5).ok()?;
+        match self.beatmap.take() {
+            // the star rating follows the menu mods and ruleset, it comes
+            // from the difficulty cache as long as they don't change
+            Some(mut beatmap) if beatmap.technical.md5 == current => {
+                if let Ok(star_rating) = beatmap::stable::file::star_rating(p, state) {
+                    beatmap.stats.star_rating = star_rating;
+                }
+                Some(beatmap)
+            }
+            _ => beatmap::stable::memory::info_at(p, state, addr).ok(),
+        }
+    }
+}
+
+fn read_gameplay<P: MemorySource>(p: &P, state: &mut State) -> Option<GameplayInfo> {
+    let offsets = offsets();
+    let rulesets = rulesets_addr_unchecked(p, state).ok()?;
+    let ruleset = p.read_i32(rulesets + offsets.gameplay.addr).ok()?;
+    let base = p.read_i32(ruleset + offsets.gameplay.base).ok()?;
+    gameplay::stable::memory::info_at(p, state, base).ok()
+}
+
+fn read_result_screen<P: MemorySource>(p: &P, state: &mut State) -> Option<ResultScreenInfo> {
+    let offsets = offsets();
+    let ptr = result_screen_ptr_unchecked(p, state).ok()?;
+    let addr = p.read_i32(ptr + offsets.result_screen.addr).ok()?;
+    let base = p.read_i32(addr + offsets.result_screen.base).ok()?;
+    resultscreen::stable::memory::info_at(p, state, base).ok()
+}
+
+#[cfg(feature = "serde")]
+fn to_value(snapshot: &OsuSnapshot) -> Result<serde_json::Value, Error> {
+    serde_json::to_value(snapshot).map_err(|e| Error::Parse(e.to_string()))
+}
@@ -2,16 +2,23 @@
 #[cfg(feature = "parallel-read")]
 use rayon::prelude::*;
 
+use crate::memory::MemorySource;
+use crate::reader::offsets::offsets;
 use crate::Error;
-use rosu_mem::{
-    process::{Process, ProcessTraits},
-    signature::Signature,
-};
+use rosu_mem::signature::Signature;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::Mutex;
 use std::time::Instant;
 
-#[derive(Default, Clone)]
+/// Addresses found by scanning for the signatures of the current
+/// [`offsets`](crate::reader::offsets::offsets) table.
+///
+/// A field left at `0` has not been resolved yet, [`State::address`] scans for
+/// it on first use. Signatures that could not be found are remembered so the
+/// scan isn't repeated on every read, see [`StaticAddresses::retry_missing`].
+#[derive(Debug, Default, Clone)]
 pub struct StaticAddresses {
     pub base: i32,
     pub status: i32,
@@ -25,157 +32,446 @@ pub struct StaticAddresses {
     pub settings: i32,
     pub user_profile: i32,
     pub check_replay_addr: i32,
+    missing: HashMap<SignatureName, String>,
+}
+
+/// Signatures osu! is scanned for, one per [`StaticAddresses`] field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureName {
+    Base,
+    Status,
+    MenuMods,
+    Rulesets,
+    Playtime,
+    Skin,
+    ChatChecker,
+    AudioTimeBase,
+    IgTimeBase,
+    Settings,
+    UserProfile,
+    CheckReplayAddr,
+}
+
+impl SignatureName {
+    /// In [`SIGNATURES`] order
+    pub const ALL: [SignatureName; 12] = [
+        SignatureName::Base,
+        SignatureName::Status,
+        SignatureName::MenuMods,
+        SignatureName::Rulesets,
+        SignatureName::Playtime,
+        SignatureName::Skin,
+        SignatureName::ChatChecker,
+        SignatureName::AudioTimeBase,
+        SignatureName::IgTimeBase,
+        SignatureName::Settings,
+        SignatureName::UserProfile,
+        SignatureName::CheckReplayAddr,
+    ];
+
+    /// Name of the matching `StaticAddresses` field
+    pub fn name(&self) -> &'static str {
+        match self {
+            SignatureName::Base => "base",
+            SignatureName::Status => "status",
+            SignatureName::MenuMods => "menu_mods",
+            SignatureName::Rulesets => "rulesets",
+            SignatureName::Playtime => "playtime",
+            SignatureName::Skin => "skin",
+            SignatureName::ChatChecker => "chat_checker",
+            SignatureName::AudioTimeBase => "audio_time_base",
+            SignatureName::IgTimeBase => "ig_time_base",
+            SignatureName::Settings => "settings",
+            SignatureName::UserProfile => "user_profile",
+            SignatureName::CheckReplayAddr => "check_replay_addr",
+        }
+    }
+
+    /// Inverse of [`SignatureName::name`]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|n| n.name() == name)
+    }
+}
+
+impl std::fmt::Display for SignatureName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// Where a single signature stands, see [`StaticAddresses::report`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Not scanned yet, it will be on first use
+    Pending,
+    Resolved(i32),
+    /// Scanning failed with this error
+    Missing(String),
+}
+
+/// Group of readers sharing the same signatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    Common,
+    Beatmap,
+    Gameplay,
+    ResultScreen,
+    User,
+    Overlay,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 6] = [
+        Subsystem::Common,
+        Subsystem::Beatmap,
+        Subsystem::Gameplay,
+        Subsystem::ResultScreen,
+        Subsystem::User,
+        Subsystem::Overlay,
+    ];
+
+    /// Signatures the readers of this subsystem need, including the ones
+    /// they only reach through other subsystems (game state, selected beatmap
+    /// and its file, menu mods and ruleset)
+    pub fn signatures(&self) -> &'static [SignatureName] {
+        use SignatureName::*;
+
+        match self {
+            Subsystem::Common => &[Status, MenuMods, Playtime, Settings, CheckReplayAddr, Base],
+            Subsystem::Beatmap => &[Status, Base, Settings, MenuMods],
+            Subsystem::Gameplay => &[Status, Rulesets, Base, Playtime],
+            Subsystem::ResultScreen => &[Status, Rulesets, Base, Settings],
+            Subsystem::User => &[UserProfile],
+            Subsystem::Overlay => &[Status, Rulesets, UserProfile, Base],
+        }
+    }
 }
 
+/// Serialized without the leading underscores (`geki`, `300`, ..., `miss`)
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hit {
+    #[cfg_attr(feature = "serde", serde(rename = "geki"))]
     pub _geki: i16,
+    #[cfg_attr(feature = "serde", serde(rename = "300"))]
     pub _300: i16,
+    #[cfg_attr(feature = "serde", serde(rename = "katu"))]
     pub _katu: i16,
+    #[cfg_attr(feature = "serde", serde(rename = "100"))]
     pub _100: i16,
+    #[cfg_attr(feature = "serde", serde(rename = "50"))]
     pub _50: i16,
+    #[cfg_attr(feature = "serde", serde(rename = "miss"))]
     pub _miss: i16,
 }
 
-#[derive(Debug, Clone)]
-pub(crate) struct SignatureBase {
-    base_sig: &'static str,
-    status_sig: &'static str,
-    menu_mods_sig: &'static str,
-    rulesets_sig: &'static str,
-    playtime_sig: &'static str,
-    skin_sig: &'static str,
-    chat_checker_sig: &'static str,
-    audio_time_base_sig: &'static str,
-    ig_time_base_sig: &'static str,
-    settings_sig: &'static str,
-    user_profile_sig: &'static str,
-    check_replay_addr: &'static str,
-}
-
-pub(crate) const SIGNATURES: SignatureBase = SignatureBase {
-    base_sig: "F8 01 74 04 83 65",
-    status_sig: "48 83 F8 04 73 1E",
-    menu_mods_sig: "C8 FF ?? ?? ?? ?? ?? 81 0D ?? ?? ?? ?? 00 08 00 00",
-    rulesets_sig: "7D 15 A1 ?? ?? ?? ?? 85 C0",
-    playtime_sig: "5E 5F 5D C3 A1 ?? ?? ?? ?? 89 ?? 04",
-    skin_sig: "74 2C 85 FF 75 28 A1 ?? ?? ?? ?? 8D 15",
-    chat_checker_sig: "0A D7 23 3C 00 00 ?? 01", // modified back again caused crash
-    audio_time_base_sig: "55 8B EC 83 EC 08 A1 ?? ?? ?? ?? 85 C0", // need to use  0x7 offset
-    ig_time_base_sig: "EB 0A A1 ?? ?? ?? ?? A3",
-    settings_sig: "83 E0 20 85 C0 7E 2F",
-    user_profile_sig: "FF 15 ?? ?? ?? ?? A1 ?? ?? ?? ?? 8B 48 54 33 D2",
-    check_replay_addr: "55 8B EC 80 3D ?? ?? ?? ?? 00 75 26 80 3D",
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct SignatureBase {
+    pub base_sig: Cow<'static, str>,
+    pub status_sig: Cow<'static, str>,
+    pub menu_mods_sig: Cow<'static, str>,
+    pub rulesets_sig: Cow<'static, str>,
+    pub playtime_sig: Cow<'static, str>,
+    pub skin_sig: Cow<'static, str>,
+    pub chat_checker_sig: Cow<'static, str>,
+    pub audio_time_base_sig: Cow<'static, str>,
+    pub ig_time_base_sig: Cow<'static, str>,
+    pub settings_sig: Cow<'static, str>,
+    pub user_profile_sig: Cow<'static, str>,
+    pub check_replay_addr: Cow<'static, str>,
+}
+
+pub const SIGNATURES: SignatureBase = SignatureBase {
+    base_sig: Cow::Borrowed("F8 01 74 04 83 65"),
+    status_sig: Cow::Borrowed("48 83 F8 04 73 1E"),
+    menu_mods_sig: Cow::Borrowed("C8 FF ?? ?? ?? ?? ?? 81 0D ?? ?? ?? ?? 00 08 00 00"),
+    rulesets_sig: Cow::Borrowed("7D 15 A1 ?? ?? ?? ?? 85 C0"),
+    playtime_sig: Cow::Borrowed("5E 5F 5D C3 A1 ?? ?? ?? ?? 89 ?? 04"),
+    skin_sig: Cow::Borrowed("74 2C 85 FF 75 28 A1 ?? ?? ?? ?? 8D 15"),
+    chat_checker_sig: Cow::Borrowed("0A D7 23 3C 00 00 ?? 01"), // modified back again caused crash
+    audio_time_base_sig: Cow::Borrowed("55 8B EC 83 EC 08 A1 ?? ?? ?? ?? 85 C0"), // need to use  0x7 offset
+    ig_time_base_sig: Cow::Borrowed("EB 0A A1 ?? ?? ?? ?? A3"),
+    settings_sig: Cow::Borrowed("83 E0 20 85 C0 7E 2F"),
+    user_profile_sig: Cow::Borrowed("FF 15 ?? ?? ?? ?? A1 ?? ?? ?? ?? 8B 48 54 33 D2"),
+    check_replay_addr: Cow::Borrowed("55 8B EC 80 3D ?? ?? ?? ?? 00 75 26 80 3D"),
 };
 
+impl Default for SignatureBase {
+    fn default() -> Self {
+        SIGNATURES
+    }
+}
+
+impl SignatureBase {
+    /// Every signature paired with the `StaticAddresses` field it resolves
+    pub fn entries(&self) -> [(SignatureName, &str); 12] {
+        [
+            (SignatureName::Base, &self.base_sig),
+            (SignatureName::Status, &self.status_sig),
+            (SignatureName::MenuMods, &self.menu_mods_sig),
+            (SignatureName::Rulesets, &self.rulesets_sig),
+            (SignatureName::Playtime, &self.playtime_sig),
+            (SignatureName::Skin, &self.skin_sig),
+            (SignatureName::ChatChecker, &self.chat_checker_sig),
+            (SignatureName::AudioTimeBase, &self.audio_time_base_sig),
+            (SignatureName::IgTimeBase, &self.ig_time_base_sig),
+            (SignatureName::Settings, &self.settings_sig),
+            (SignatureName::UserProfile, &self.user_profile_sig),
+            (SignatureName::CheckReplayAddr, &self.check_replay_addr),
+        ]
+    }
+
+    /// Pattern of the signature `name`
+    pub fn get(&self, name: SignatureName) -> &str {
+        self.entries()
+            .into_iter()
+            .find(|(n, _)| *n == name)
+            .map_or("", |(_, sig)| sig)
+    }
+
+    /// Name of the entry `sign` was parsed from. Entries are parsed before
+    /// comparing, so case and formatting of the table strings don't matter
+    pub fn name_of(&self, sign: &Signature) -> Option<SignatureName> {
+        let sign = sign.to_string();
+        self.entries()
+            .into_iter()
+            .find(|(_, sig)| Signature::from_str(sig).is_ok_and(|sig| sig.to_string() == sign))
+            .map(|(name, _)| name)
+    }
+}
+
 impl StaticAddresses {
-    pub fn new(p: &Process) -> Result<Self, Error> {
+    /// Returns the field matching `name`
+    pub(crate) fn get(&self, name: SignatureName) -> i32 {
+        match name {
+            SignatureName::Base => self.base,
+            SignatureName::Status => self.status,
+            SignatureName::MenuMods => self.menu_mods,
+            SignatureName::Rulesets => self.rulesets,
+            SignatureName::Playtime => self.playtime,
+            SignatureName::Skin => self.skin,
+            SignatureName::ChatChecker => self.chat_checker,
+            SignatureName::AudioTimeBase => self.audio_time_base,
+            SignatureName::IgTimeBase => self.ig_time_base,
+            SignatureName::Settings => self.settings,
+            SignatureName::UserProfile => self.user_profile,
+            SignatureName::CheckReplayAddr => self.check_replay_addr,
+        }
+    }
+
+    /// Sets the field matching `name`
+    pub(crate) fn set(&mut self, name: SignatureName, addr: i32) {
+        self.missing.remove(&name);
+        match name {
+            SignatureName::Base => self.base = addr,
+            SignatureName::Status => self.status = addr,
+            SignatureName::MenuMods => self.menu_mods = addr,
+            SignatureName::Rulesets => self.rulesets = addr,
+            SignatureName::Playtime => self.playtime = addr,
+            SignatureName::Skin => self.skin = addr,
+            SignatureName::ChatChecker => self.chat_checker = addr,
+            SignatureName::AudioTimeBase => self.audio_time_base = addr,
+            SignatureName::IgTimeBase => self.ig_time_base = addr,
+            SignatureName::Settings => self.settings = addr,
+            SignatureName::UserProfile => self.user_profile = addr,
+            SignatureName::CheckReplayAddr => self.check_replay_addr = addr,
+        }
+    }
+
+    /// Where the signature `name` stands
+    pub fn status_of(&self, name: SignatureName) -> SignatureStatus {
+        if let Some(error) = self.missing.get(&name) {
+            return SignatureStatus::Missing(error.clone());
+        }
+        match self.get(name) {
+            0 => SignatureStatus::Pending,
+            addr => SignatureStatus::Resolved(addr),
+        }
+    }
+
+    /// Status of every signature, in [`SIGNATURES`] order
+    pub fn report(&self) -> Vec<(SignatureName, SignatureStatus)> {
+        SignatureName::ALL
+            .into_iter()
+            .map(|name| (name, self.status_of(name)))
+            .collect()
+    }
+
+    /// Signatures `subsystem` needs that could not be found, an empty list
+    /// means its readers can be used (pending signatures are resolved on use)
+    pub fn missing(&self, subsystem: Subsystem) -> Vec<SignatureName> {
+        subsystem
+            .signatures()
+            .iter()
+            .copied()
+            .filter(|name| self.missing.contains_key(name))
+            .collect()
+    }
+
+    /// Forgets every failed scan so the next use tries again, useful after
+    /// osu! finished loading
+    pub fn retry_missing(&mut self) {
+        self.missing.clear();
+    }
+
+    /// Scans every signature up front.
+    ///
+    /// Signatures that can't be found don't fail the whole scan, they are
+    /// reported by [`StaticAddresses::report`] and readers needing them return
+    /// `Error::NotAvailable`. Only a missing `status` signature is an error
+    /// since nothing can be read without knowing the game state.
+    pub fn new<P: MemorySource>(p: &P) -> Result<Self, Error> {
         let start = Instant::now();
-        println!("Reading signatures...");
+        let parallel = cfg!(feature = "parallel-read");
+        tracing::debug!(parallel, "reading signatures");
 
         #[cfg(feature = "parallel-read")]
-        {
-            println!("Using parallel signature reading with rayon...");
-            Self::new_parallel(p, start)
-        }
+        let addresses = Self::new_parallel(p);
 
         #[cfg(not(feature = "parallel-read"))]
-        {
-            println!("Using sequential signature reading...");
-            Self::new_sequential(p, start)
+        let addresses = Self::new_sequential(p);
+
+        addresses.require(REQUIRED)?;
+        tracing::info!(elapsed = ?start.elapsed(), ?addresses, "signatures read");
+        Ok(addresses)
+    }
+
+    /// Only scans the signatures needed to tell the game state, everything
+    /// else is resolved on first use through [`State::address`]
+    pub fn lazy<P: MemorySource>(p: &P) -> Result<Self, Error> {
+        let mut addresses = Self::default();
+        for name in REQUIRED {
+            addresses.resolve(p, *name)?;
+        }
+        Ok(addresses)
+    }
+
+    fn require(&self, names: &[SignatureName]) -> Result<(), Error> {
+        for name in names {
+            if let Some(error) = self.missing.get(name) {
+                return Err(Error::NotAvailable(format!(
+                    "Signature {name} is not available: {error}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Address of the signature `name`, scanning for it if it is still pending
+    pub(crate) fn resolve<P: MemorySource>(
+        &mut self,
+        p: &P,
+        name: SignatureName,
+    ) -> Result<i32, Error> {
+        match self.status_of(name) {
+            SignatureStatus::Resolved(addr) => Ok(addr),
+            SignatureStatus::Missing(error) => Err(Error::NotAvailable(format!(
+                "Signature {name} is not available: {error}"
+            ))),
+            SignatureStatus::Pending => {
+                let table = offsets();
+                self.record(name, Self::read_one(p, name, table.signatures.get(name)));
+                self.resolve(p, name)
+            }
+        }
+    }
+
+    fn record(&mut self, name: SignatureName, result: Result<i32, Error>) {
+        match result {
+            Ok(addr) => self.set(name, addr),
+            Err(e) => {
+                self.missing.insert(name, e.to_string());
+            }
+        }
+    }
+
+    /// Resolves a single signature inside its own timing span
+    fn read_one<P: MemorySource>(p: &P, name: SignatureName, sig: &str) -> Result<i32, Error> {
+        let _span = tracing::debug_span!("signature", name = name.name(), pattern = sig).entered();
+        let start = Instant::now();
+
+        let result = Signature::from_str(sig)
+            .map_err(Error::from)
+            .and_then(|signature| p.read_signature(&signature));
+        match &result {
+            Ok(addr) => {
+                tracing::debug!(addr = format_args!("{addr:#x}"), elapsed = ?start.elapsed(), "signature resolved")
+            }
+            Err(e) => {
+                tracing::warn!(name = name.name(), error = %e, elapsed = ?start.elapsed(), "signature not found")
+            }
         }
+        result
     }
 
     #[cfg(feature = "parallel-read")]
-    fn new_parallel(p: &Process, start: Instant) -> Result<Self, Error> {
+    fn new_parallel<P: MemorySource>(p: &P) -> Self {
         use rayon::prelude::*;
 
-        let signatures = [
-            ("base", SIGNATURES.base_sig),
-            ("status", SIGNATURES.status_sig),
-            ("menu_mods", SIGNATURES.menu_mods_sig),
-            ("rulesets", SIGNATURES.rulesets_sig),
-            ("playtime", SIGNATURES.playtime_sig),
-            ("skin", SIGNATURES.skin_sig),
-            ("chat_checker", SIGNATURES.chat_checker_sig),
-            ("audio_time_base", SIGNATURES.audio_time_base_sig),
-            ("ig_time_base", SIGNATURES.ig_time_base_sig),
-            ("settings", SIGNATURES.settings_sig),
-            ("user_profile", SIGNATURES.user_profile_sig),
-            ("check_replay_addr", SIGNATURES.check_replay_addr),
-        ];
-
-        let results: HashMap<&str, i32> = signatures
+        let table = offsets();
+        let signatures = table.signatures.entries();
+
+        let results: Vec<(SignatureName, Result<i32, Error>)> = signatures
             .par_iter()
-            .map(|(name, sig)| {
-                let signature = Signature::from_str(sig)?;
-                let addr = p.read_signature(&signature)?;
-                Ok::<_, Error>((*name, addr))
-            })
-            .collect::<Result<_, Error>>()?;
-
-        println!("Time taken: {:?}", start.elapsed());
-
-        Ok(Self {
-            base: results["base"],
-            status: results["status"],
-            menu_mods: results["menu_mods"],
-            rulesets: results["rulesets"],
-            playtime: results["playtime"],
-            skin: results["skin"],
-            chat_checker: results["chat_checker"],
-            audio_time_base: results["audio_time_base"],
-            ig_time_base: results["ig_time_base"],
-            settings: results["settings"],
-            user_profile: results["user_profile"],
-            check_replay_addr: results["check_replay_addr"],
-        })
+            .map(|(name, sig)| (*name, Self::read_one(p, *name, sig)))
+            .collect();
+
+        let mut addresses = Self::default();
+        for (name, result) in results {
+            addresses.record(name, result);
+        }
+        addresses
     }
 
     #[cfg(not(feature = "parallel-read"))]
-    fn new_sequential(p: &Process, start: Instant) -> Result<Self, Error> {
-        let signatures = [
-            ("base", SIGNATURES.base_sig),
-            ("status", SIGNATURES.status_sig),
-            ("menu_mods", SIGNATURES.menu_mods_sig),
-            ("rulesets", SIGNATURES.rulesets_sig),
-            ("playtime", SIGNATURES.playtime_sig),
-            ("skin", SIGNATURES.skin_sig),
-            ("chat_checker", SIGNATURES.chat_checker_sig),
-            ("audio_time_base", SIGNATURES.audio_time_base_sig),
-            ("ig_time_base", SIGNATURES.ig_time_base_sig),
-            ("settings", SIGNATURES.settings_sig),
-            ("user_profile", SIGNATURES.user_profile_sig),
-            ("check_replay_addr", SIGNATURES.check_replay_addr),
-        ];
-
-        let mut results = HashMap::new();
-        for (name, sig) in signatures.iter() {
-            let signature = Signature::from_str(sig)?;
-            let addr = p.read_signature(&signature)?;
-            results.insert(*name, addr);
+    fn new_sequential<P: MemorySource>(p: &P) -> Self {
+        let table = offsets();
+        let mut addresses = Self::default();
+        for (name, sig) in table.signatures.entries() {
+            addresses.record(name, Self::read_one(p, name, sig));
         }
-
-        println!("Time taken: {:?}", start.elapsed());
-
-        Ok(Self {
-            base: results["base"],
-            status: results["status"],
-            menu_mods: results["menu_mods"],
-            rulesets: results["rulesets"],
-            playtime: results["playtime"],
-            skin: results["skin"],
-            chat_checker: results["chat_checker"],
-            audio_time_base: results["audio_time_base"],
-            ig_time_base: results["ig_time_base"],
-            settings: results["settings"],
-            user_profile: results["user_profile"],
-            check_replay_addr: results["check_replay_addr"],
-        })
+        addresses
     }
 }
 
-#[derive(Default, Clone)]
+/// Signatures attaching can't go without
+const REQUIRED: &[SignatureName] = &[SignatureName::Status];
+
+#[derive(Debug, Default, Clone)]
 pub struct State {
     pub addresses: StaticAddresses,
 }
+
+impl State {
+    /// Address of the signature `name`, resolved on first use.
+    ///
+    /// Fails with `Error::NotAvailable` naming the signature if it can't be found.
+    pub fn address<P: MemorySource>(&mut self, p: &P, name: SignatureName) -> Result<i32, Error> {
+        self.addresses.resolve(p, name)
+    }
+}
+
+/// How readers get to the `State`, either borrowed for the reader lifetime or
+/// shared behind a lock that is only held for the duration of one read
+pub trait StateHandle {
+    fn with_state<R>(&mut self, f: impl FnOnce(&mut State) -> R) -> R;
+}
+
+impl StateHandle for &mut State {
+    fn with_state<R>(&mut self, f: impl FnOnce(&mut State) -> R) -> R {
+        f(self)
+    }
+}
+
+impl StateHandle for &Mutex<State> {
+    fn with_state<R>(&mut self, f: impl FnOnce(&mut State) -> R) -> R {
+        // State is plain data, a panic while holding it can't leave it half written
+        let mut state = self.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut state)
+    }
+}
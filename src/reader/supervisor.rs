@@ -0,0 +1,215 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+use crate::memory::MemorySource;
+use crate::reader::common::GameState;
+use crate::reader::session::OsuSession;
+use crate::reader::structs::State;
+use crate::reader::try_attach;
+use crate::reader::wait::Waiter;
+use crate::Error;
+use rosu_mem::process::Process;
+
+/// Why the supervisor dropped its session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetachReason {
+    /// The process is gone
+    ProcessExited,
+    /// osu! reported `GameState::Exit`
+    GameExit,
+    /// This many reads in a row failed with `Error::MemoryRead`
+    ReadFailures(u32),
+}
+
+/// Sent to [`Supervisor::subscribe`] receivers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    Attached,
+    Detached(DetachReason),
+}
+
+type AttachFn<P> = Box<dyn FnMut() -> Result<(State, P), Error> + Send>;
+
+/// Keeps an [`OsuSession`] alive across osu! restarts.
+///
+/// Every read goes through [`Supervisor::read`], which counts consecutive
+/// memory read failures and drops the session once the process is gone,
+/// osu! is exiting or too many reads failed. The process and game state are
+/// also checked every `check_interval` while reads succeed. The next read
+/// attaches again.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rosu_memory_lib::reader::supervisor::Supervisor;
+///
+/// let mut supervisor = Supervisor::new();
+/// let notifications = supervisor.subscribe();
+/// loop {
+///     match supervisor.read(|session| session.gameplay().combo()) {
+///         Ok(combo) => println!("combo: {combo}"),
+///         Err(e) => println!("Error: {e}"),
+///     }
+///     while let Ok(event) = notifications.try_recv() {
+///         println!("{event:?}");
+///     }
+///     std::thread::sleep(std::time::Duration::from_millis(100));
+/// }
+/// ```
+pub struct Supervisor<P: MemorySource = Process> {
+    session: Option<OsuSession<P>>,
+    attach: AttachFn<P>,
+    failure_threshold: u32,
+    failures: u32,
+    check_interval: Duration,
+    last_check: Instant,
+    subscribers: Vec<Sender<ConnectionEvent>>,
+}
+
+impl Default for Supervisor<Process> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Supervisor<Process> {
+    /// Supervises the osu!.exe process
+    pub fn new() -> Self {
+        Self::with_attach(try_attach)
+    }
+}
+
+impl<P: MemorySource> Supervisor<P> {
+    /// Supervises whatever `attach` returns, it is called once per attach
+    /// attempt and should fail fast when nothing can be attached to
+    pub fn with_attach<F>(attach: F) -> Self
+    where
+        F: FnMut() -> Result<(State, P), Error> + Send + 'static,
+    {
+        Self {
+            session: None,
+            attach: Box::new(attach),
+            failure_threshold: 10,
+            failures: 0,
+            check_interval: Duration::from_secs(1),
+            last_check: Instant::now(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Consecutive `Error::MemoryRead` failures before detaching, defaults to 10
+    pub fn failure_threshold(mut self, threshold: u32) -> Self {
+        self.failure_threshold = threshold.max(1);
+        self
+    }
+
+    /// How often [`Supervisor::read`] runs [`Supervisor::check`] on its own,
+    /// defaults to 1s. `Duration::ZERO` checks before every read
+    pub fn check_interval(mut self, interval: Duration) -> Self {
+        self.check_interval = interval;
+        self
+    }
+
+    /// Receives every `Attached`/`Detached` transition from now on
+    pub fn subscribe(&mut self) -> Receiver<ConnectionEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers.push(tx);
+        rx
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Current session, attaching once if there is none
+    pub fn try_session(&mut self) -> Result<&OsuSession<P>, Error> {
+        if self.session.is_none() {
+            let (state, process) = (self.attach)()?;
+            self.session = Some(OsuSession::new(process, state));
+            self.failures = 0;
+            self.last_check = Instant::now();
+            tracing::info!("attached");
+            self.notify(ConnectionEvent::Attached);
+        }
+        self.session
+            .as_ref()
+            .ok_or_else(|| Error::NotAvailable("Not attached".to_string()))
+    }
+
+    /// Current session, retrying to attach according to `waiter`
+    pub fn session(&mut self, waiter: &Waiter) -> Result<&OsuSession<P>, Error> {
+        waiter.until(|| match self.try_session() {
+            Ok(_) => Ok(Some(())),
+            Err(e) => {
+                tracing::debug!(error = %e, "attach failed, retrying");
+                Ok(None)
+            }
+        })?;
+        self.try_session()
+    }
+
+    /// Runs `f` on the current session, attaching first if needed.
+    ///
+    /// `Error::NotAvailable` when osu! is not running, otherwise whatever `f` returned.
+    pub fn read<T, F>(&mut self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&OsuSession<P>) -> Result<T, Error>,
+    {
+        // catch exits between failing reads, `check` is a no-op when detached
+        if self.last_check.elapsed() >= self.check_interval {
+            self.check();
+        }
+
+        let session = self
+            .try_session()
+            .map_err(|e| Error::NotAvailable(format!("osu! is not attached: {e}")))?;
+
+        let result = f(session);
+        match &result {
+            Err(Error::MemoryRead(_)) => {
+                self.failures += 1;
+                self.check();
+            }
+            _ => self.failures = 0,
+        }
+        result
+    }
+
+    /// Drops the session if the process is dead, osu! is exiting or reads
+    /// have been failing, returns whether a session is still held
+    pub fn check(&mut self) -> bool {
+        self.last_check = Instant::now();
+        let Some(session) = &self.session else {
+            return false;
+        };
+
+        let reason = if !session.process().is_alive() {
+            Some(DetachReason::ProcessExited)
+        } else if self.failures >= self.failure_threshold {
+            Some(DetachReason::ReadFailures(self.failures))
+        } else if session.common().game_state().ok() == Some(GameState::Exit) {
+            Some(DetachReason::GameExit)
+        } else {
+            None
+        };
+
+        match reason {
+            Some(reason) => {
+                self.detach(reason);
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn detach(&mut self, reason: DetachReason) {
+        self.session = None;
+        self.failures = 0;
+        tracing::warn!(?reason, "detached");
+        self.notify(ConnectionEvent::Detached(reason));
+    }
+
+    fn notify(&mut self, event: ConnectionEvent) {
+        self.subscribers.retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
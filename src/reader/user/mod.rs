@@ -1,19 +1,20 @@
 pub mod common;
 pub mod stable;
 use crate::impl_osu_accessor;
+use crate::memory::MemorySource;
 use crate::reader::common::OsuClientKind;
-use crate::reader::structs::State;
+use crate::reader::structs::{State, StateHandle};
 use crate::reader::user::common::UserInfo;
 use crate::Error;
 use rosu_mem::process::Process;
-pub struct UserReader<'a> {
-    pub process: &'a Process,
-    pub state: &'a mut State,
+pub struct UserReader<'a, P: MemorySource = Process, S: StateHandle = &'a mut State> {
+    pub process: &'a P,
+    pub state: S,
     pub osu_type: OsuClientKind,
 }
 
-impl<'a> UserReader<'a> {
-    pub fn new(p: &'a Process, state: &'a mut State, osu_type: OsuClientKind) -> Self {
+impl<'a, P: MemorySource, S: StateHandle> UserReader<'a, P, S> {
+    pub fn new(p: &'a P, state: S, osu_type: OsuClientKind) -> Self {
         Self {
             process: p,
             state,
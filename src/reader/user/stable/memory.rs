@@ -1,34 +1,37 @@
 use crate::generate_offset_getter;
+use crate::memory::MemorySource;
 use crate::reader::helpers::{read_f32, read_f64, read_i32, read_i64, read_string};
-use crate::reader::structs::State;
+use crate::reader::offsets::offsets;
+use crate::reader::structs::{SignatureName, State};
 use crate::reader::user::common::UserInfo;
-use crate::reader::user::stable::offset::USER_PROFILE_OFFSET;
 use crate::Error;
-use rosu_mem::process::{Process, ProcessTraits};
 use std::mem::size_of;
 
-pub fn user_base(p: &Process, state: &mut State) -> Result<i32, Error> {
-    Ok(p.read_i32(p.read_i32(state.addresses.user_profile + USER_PROFILE_OFFSET.ptr)?)?)
+pub fn user_base<P: MemorySource>(p: &P, state: &mut State) -> Result<i32, Error> {
+    p.read_i32(
+        p.read_i32(state.address(p, SignatureName::UserProfile)? + offsets().user_profile.ptr)?,
+    )
 }
 generate_offset_getter! {
-    id: i32 = read_i32(USER_PROFILE_OFFSET.id, user_base);
-    bancho_status: i32 = read_i32(USER_PROFILE_OFFSET.bancho_status, user_base);
-    country_code: i32 = read_i32(USER_PROFILE_OFFSET.country_code, user_base);
-    username: String = read_string(USER_PROFILE_OFFSET.username, user_base);
-    pp: i32 = read_i32(USER_PROFILE_OFFSET.pp, user_base);
-    rankedscore: i64 = read_i64(USER_PROFILE_OFFSET.rankedscore, user_base);
-    level: f32 = read_f32(USER_PROFILE_OFFSET.level, user_base);
-    playcount: i32 = read_i32(USER_PROFILE_OFFSET.playcount, user_base);
-    rank: i32 = read_i32(USER_PROFILE_OFFSET.rank, user_base);
-    playmode: i32 = read_i32(USER_PROFILE_OFFSET.playmode, user_base);
-    accuracy: f64 = read_f64(USER_PROFILE_OFFSET.accuracy, user_base);
+    id: i32 = read_i32(offsets().user_profile.id, user_base);
+    bancho_status: i32 = read_i32(offsets().user_profile.bancho_status, user_base);
+    country_code: i32 = read_i32(offsets().user_profile.country_code, user_base);
+    username: String = read_string(offsets().user_profile.username, user_base);
+    pp: i32 = read_i32(offsets().user_profile.pp, user_base);
+    rankedscore: i64 = read_i64(offsets().user_profile.rankedscore, user_base);
+    level: f32 = read_f32(offsets().user_profile.level, user_base);
+    playcount: i32 = read_i32(offsets().user_profile.playcount, user_base);
+    rank: i32 = read_i32(offsets().user_profile.rank, user_base);
+    playmode: i32 = read_i32(offsets().user_profile.playmode, user_base);
+    accuracy: f64 = read_f64(offsets().user_profile.accuracy, user_base);
 }
 
-pub fn info(p: &Process, state: &mut State) -> Result<UserInfo, Error> {
+pub fn info<P: MemorySource>(p: &P, state: &mut State) -> Result<UserInfo, Error> {
+    let offsets = offsets();
     let user_profile_base = user_base(p, state)?;
     let mut buffer = [0u8; size_of::<i32>() * 5];
     p.read(
-        user_profile_base + USER_PROFILE_OFFSET.playcount,
+        user_profile_base + offsets.user_profile.playcount,
         size_of::<i32>() * 5,
         &mut buffer,
     )?;
@@ -39,16 +42,16 @@ pub fn info(p: &Process, state: &mut State) -> Result<UserInfo, Error> {
     let bancho_status = i32::from_le_bytes(buffer[16..20].try_into().unwrap());
 
     let user_profile = UserInfo {
-        id: p.read_i32(user_profile_base + USER_PROFILE_OFFSET.id)?,
-        username: p.read_string(user_profile_base + USER_PROFILE_OFFSET.username)?,
+        id: p.read_i32(user_profile_base + offsets.user_profile.id)?,
+        username: p.read_string(user_profile_base + offsets.user_profile.username)?,
         pp,
-        rankedscore: p.read_i64(user_profile_base + USER_PROFILE_OFFSET.rankedscore)?,
-        level: p.read_f32(user_profile_base + USER_PROFILE_OFFSET.level)?,
+        rankedscore: p.read_i64(user_profile_base + offsets.user_profile.rankedscore)?,
+        level: p.read_f32(user_profile_base + offsets.user_profile.level)?,
         playcount,
         rank,
         playmode,
-        accuracy: p.read_f64(user_profile_base + USER_PROFILE_OFFSET.accuracy)?,
-        country_code: p.read_i32(user_profile_base + USER_PROFILE_OFFSET.country_code)?,
+        accuracy: p.read_f64(user_profile_base + offsets.user_profile.accuracy)?,
+        country_code: p.read_i32(user_profile_base + offsets.user_profile.country_code)?,
         bancho_status,
     };
     Ok(user_profile)
@@ -1,3 +1,9 @@
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct UserProfileOffset {
     pub ptr: i32,
     pub id: i32,
@@ -13,7 +19,7 @@ pub struct UserProfileOffset {
     pub bancho_status: i32,
 }
 
-pub(crate) const USER_PROFILE_OFFSET: UserProfileOffset = UserProfileOffset {
+pub const USER_PROFILE_OFFSET: UserProfileOffset = UserProfileOffset {
     ptr: 0x7,
     accuracy: 0x4,
     rankedscore: 0xC,
@@ -27,3 +33,9 @@ pub(crate) const USER_PROFILE_OFFSET: UserProfileOffset = UserProfileOffset {
     bancho_status: 0x8c, // end read
     country_code: 0x9c,
 };
+
+impl Default for UserProfileOffset {
+    fn default() -> Self {
+        USER_PROFILE_OFFSET
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::memory::MemorySource;
+use crate::reader::common::stable::memory::game_state;
+use crate::reader::common::GameState;
+use crate::reader::structs::State;
+use crate::Error;
+
+/// Shared flag used to stop a [`Waiter`] from another thread, cancelling
+/// wakes up anything sleeping on the token right away
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<(Mutex<bool>, Condvar)>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        let (cancelled, wake) = &*self.0;
+        *cancelled.lock().unwrap_or_else(|e| e.into_inner()) = true;
+        wake.notify_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.0 .0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Sleeps for `duration` or until the token is cancelled, returns whether
+    /// it was cancelled
+    pub fn sleep(&self, duration: Duration) -> bool {
+        let (cancelled, wake) = &*self.0;
+        let cancelled = cancelled.lock().unwrap_or_else(|e| e.into_inner());
+        let (cancelled, _) = wake
+            .wait_timeout_while(cancelled, duration, |cancelled| !*cancelled)
+            .unwrap_or_else(|e| e.into_inner());
+        *cancelled
+    }
+}
+
+/// Configurable polling loop.
+///
+/// Polls every `interval` (50ms by default), optionally growing the interval
+/// after each unsuccessful poll, and gives up with [`Error::Timeout`] or
+/// [`Error::Cancelled`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use rosu_memory_lib::init_loop;
+/// use rosu_memory_lib::reader::common::GameState;
+/// use rosu_memory_lib::reader::wait::Waiter;
+///
+/// let (mut state, process) = init_loop(500)?;
+/// let reached = Waiter::new()
+///     .interval(Duration::from_millis(100))
+///     .timeout(Duration::from_secs(60))
+///     .for_game_states(&process, &mut state, &[GameState::Playing, GameState::ResultScreen])?;
+/// println!("Now in {reached:?}");
+/// # Ok::<(), rosu_memory_lib::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Waiter {
+    interval: Duration,
+    backoff: Option<(f64, Duration)>,
+    timeout: Option<Duration>,
+    token: Option<CancellationToken>,
+}
+
+impl Default for Waiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Waiter {
+    pub fn new() -> Self {
+        Self {
+            interval: Duration::from_millis(50),
+            backoff: None,
+            timeout: None,
+            token: None,
+        }
+    }
+
+    /// Time slept between two polls
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Multiplies the interval by `factor` after every unsuccessful poll, never
+    /// sleeping longer than `max`. Factors below 1 (or NaN) keep the interval
+    /// as is
+    pub fn backoff(mut self, factor: f64, max: Duration) -> Self {
+        let factor = if factor >= 1.0 { factor } else { 1.0 };
+        self.backoff = Some((factor, max));
+        self
+    }
+
+    /// Gives up with [`Error::Timeout`] once `timeout` has elapsed
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Gives up with [`Error::Cancelled`] as soon as `token` is cancelled
+    pub fn cancel_token(mut self, token: CancellationToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Calls `poll` until it returns `Some`, errors from `poll` are returned as is
+    pub fn until<T, F>(&self, mut poll: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Result<Option<T>, Error>,
+    {
+        let start = Instant::now();
+        let mut interval = self.interval;
+
+        loop {
+            if self.token.as_ref().is_some_and(|t| t.is_cancelled()) {
+                return Err(Error::Cancelled("Wait was cancelled".to_string()));
+            }
+            if let Some(value) = poll()? {
+                return Ok(value);
+            }
+
+            let mut sleep = interval;
+            if let Some(timeout) = self.timeout {
+                let elapsed = start.elapsed();
+                if elapsed >= timeout {
+                    return Err(Error::Timeout(format!("Gave up after {elapsed:?}")));
+                }
+                sleep = sleep.min(timeout - elapsed);
+            }
+            match &self.token {
+                Some(token) if token.sleep(sleep) => {
+                    return Err(Error::Cancelled("Wait was cancelled".to_string()));
+                }
+                Some(_) => {}
+                None => std::thread::sleep(sleep),
+            }
+
+            if let Some((factor, max)) = self.backoff {
+                // an infinite factor or a huge interval would overflow `Duration`
+                interval = Duration::try_from_secs_f64(interval.as_secs_f64() * factor)
+                    .map_or(max, |next| next.min(max));
+            }
+        }
+    }
+
+    /// Waits until the game reaches any of `states` and returns the one reached
+    pub fn for_game_states<P: MemorySource>(
+        &self,
+        p: &P,
+        state: &mut State,
+        states: &[GameState],
+    ) -> Result<GameState, Error> {
+        self.until(|| {
+            let current = game_state(p, state)?;
+            Ok(states.contains(&current).then_some(current))
+        })
+    }
+
+    /// Waits until `predicate` returns `true`
+    pub fn for_predicate<P, F>(
+        &self,
+        p: &P,
+        state: &mut State,
+        mut predicate: F,
+    ) -> Result<(), Error>
+    where
+        P: MemorySource,
+        F: FnMut(&P, &mut State) -> Result<bool, Error>,
+    {
+        self.until(|| Ok(predicate(p, state)?.then_some(())))
+    }
+}
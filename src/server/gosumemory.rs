@@ -0,0 +1,442 @@
+//! gosumemory `/ws` message.
+//!
+//! Field names follow gosumemory's JSON exactly so existing overlays work
+//! without changes. Values osu! doesn't expose to us (leaderboard, strains,
+//! hit errors...) are sent with their empty defaults.
+use crate::reader::overlay::common::Key;
+use crate::reader::structs::Hit;
+use crate::server::{display, game_state_id, mode_id, Tick};
+use rosu_mods::GameModsLegacy;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GosumemoryState {
+    pub settings: Settings,
+    pub menu: Menu,
+    pub gameplay: Gameplay,
+    pub results_screen: ResultsScreen,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    pub show_interface: bool,
+    pub folders: Folders,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Folders {
+    pub game: String,
+    pub skin: String,
+    pub songs: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Menu {
+    pub main_menu: MainMenu,
+    pub state: u32,
+    pub game_mode: u32,
+    pub is_chat_enabled: u32,
+    pub bm: Bm,
+    pub mods: Mods,
+    pub pp: MenuPp,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MainMenu {
+    pub bass_density: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bm {
+    pub time: BmTime,
+    pub id: i32,
+    pub set: i32,
+    pub md5: String,
+    pub ranked_status: i16,
+    pub metadata: BmMetadata,
+    pub stats: BmStats,
+    pub path: BmPath,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BmTime {
+    pub first_obj: i32,
+    pub current: i32,
+    pub full: i32,
+    pub mp3: i32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BmMetadata {
+    pub artist: String,
+    pub artist_original: String,
+    pub title: String,
+    pub title_original: String,
+    pub mapper: String,
+    pub difficulty: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BmStats {
+    #[serde(rename = "AR")]
+    pub ar: f64,
+    #[serde(rename = "CS")]
+    pub cs: f64,
+    #[serde(rename = "OD")]
+    pub od: f64,
+    #[serde(rename = "HP")]
+    pub hp: f64,
+    #[serde(rename = "SR")]
+    pub sr: f64,
+    #[serde(rename = "BPM")]
+    pub bpm: Bpm,
+    #[serde(rename = "fullSR")]
+    pub full_sr: f64,
+    #[serde(rename = "memoryAR")]
+    pub memory_ar: f32,
+    #[serde(rename = "memoryCS")]
+    pub memory_cs: f32,
+    #[serde(rename = "memoryOD")]
+    pub memory_od: f32,
+    #[serde(rename = "memoryHP")]
+    pub memory_hp: f32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Bpm {
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BmPath {
+    /// Relative to the songs folder
+    pub full: String,
+    pub folder: String,
+    pub file: String,
+    pub bg: String,
+    pub audio: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Mods {
+    pub num: u32,
+    pub str: String,
+}
+
+impl Mods {
+    fn new(mods: GameModsLegacy) -> Self {
+        Self {
+            num: mods.bits(),
+            str: mods.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MenuPp {
+    #[serde(rename = "100")]
+    pub _100: f64,
+    #[serde(rename = "99")]
+    pub _99: f64,
+    #[serde(rename = "98")]
+    pub _98: f64,
+    #[serde(rename = "97")]
+    pub _97: f64,
+    #[serde(rename = "96")]
+    pub _96: f64,
+    #[serde(rename = "95")]
+    pub _95: f64,
+    pub strains: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Gameplay {
+    pub game_mode: u32,
+    pub name: String,
+    pub score: i32,
+    pub accuracy: f64,
+    pub combo: Combo,
+    pub hp: Hp,
+    pub hits: GameplayHits,
+    pub pp: GameplayPp,
+    pub key_overlay: KeyOverlay,
+    pub leaderboard: Leaderboard,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Combo {
+    pub current: i16,
+    pub max: i16,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Hp {
+    pub normal: f64,
+    pub smooth: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameplayHits {
+    #[serde(rename = "300")]
+    pub _300: i16,
+    pub geki: i16,
+    #[serde(rename = "100")]
+    pub _100: i16,
+    pub katu: i16,
+    #[serde(rename = "50")]
+    pub _50: i16,
+    #[serde(rename = "0")]
+    pub _0: i16,
+    pub slider_breaks: i16,
+    pub grade: Grade,
+    pub unstable_rate: f64,
+    pub hit_error_array: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Grade {
+    pub current: String,
+    pub max_this_play: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameplayPp {
+    pub current: f64,
+    pub fc: f64,
+    pub max_this_play: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct KeyOverlay {
+    pub k1: OverlayKey,
+    pub k2: OverlayKey,
+    pub m1: OverlayKey,
+    pub m2: OverlayKey,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayKey {
+    pub is_pressed: bool,
+    pub count: i32,
+}
+
+impl From<Key> for OverlayKey {
+    fn from(key: Key) -> Self {
+        Self {
+            is_pressed: key.pressed,
+            count: key.count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Leaderboard {
+    pub has_leaderboard: bool,
+    pub is_visible: bool,
+    pub ourplayer: LeaderboardPlayer,
+    pub slots: Vec<LeaderboardPlayer>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardPlayer {
+    pub name: String,
+    pub score: i32,
+    pub combo: i16,
+    pub max_combo: i16,
+    pub mods: String,
+    pub h300: i16,
+    pub h100: i16,
+    pub h50: i16,
+    pub h0: i16,
+    pub team: i32,
+    pub position: i32,
+    pub is_passing: i32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultsScreen {
+    pub name: String,
+    pub score: i32,
+    pub max_combo: i16,
+    pub mods: Mods,
+    #[serde(rename = "300")]
+    pub _300: i16,
+    pub geki: i16,
+    #[serde(rename = "100")]
+    pub _100: i16,
+    pub katu: i16,
+    #[serde(rename = "50")]
+    pub _50: i16,
+    #[serde(rename = "0")]
+    pub _0: i16,
+}
+
+impl GosumemoryState {
+    pub fn new(tick: &Tick) -> Self {
+        let snapshot = &tick.snapshot;
+        let pp = &tick.pp;
+        let mode = tick.mode();
+
+        let mut state = Self {
+            settings: Settings {
+                show_interface: false,
+                folders: Folders {
+                    game: display(tick.game_folder.as_deref()),
+                    skin: String::new(),
+                    songs: display(tick.songs_folder.as_deref()),
+                },
+            },
+            ..Default::default()
+        };
+
+        let menu = &mut state.menu;
+        menu.state = if tick.attached {
+            game_state_id(snapshot.game_state)
+        } else {
+            0
+        };
+        menu.game_mode = mode_id(mode);
+        menu.mods = Mods::new(snapshot.menu_mods.unwrap_or_default());
+        [
+            menu.pp._100,
+            menu.pp._99,
+            menu.pp._98,
+            menu.pp._97,
+            menu.pp._96,
+            menu.pp._95,
+        ] = pp.accuracy;
+
+        if let Some(beatmap) = &snapshot.beatmap {
+            let location = &beatmap.location;
+            let stats = &beatmap.stats;
+            menu.bm = Bm {
+                time: BmTime {
+                    first_obj: pp.first_object as i32,
+                    current: snapshot.game_time.unwrap_or_default(),
+                    full: stats.length,
+                    mp3: stats.length,
+                },
+                id: beatmap.technical.id,
+                set: beatmap.technical.set_id,
+                md5: beatmap.technical.md5.clone(),
+                ranked_status: beatmap.technical.ranked_status as i16,
+                metadata: BmMetadata {
+                    artist: beatmap.metadata.author.clone(),
+                    artist_original: beatmap.metadata.author.clone(),
+                    title: beatmap.metadata.title_romanized.clone(),
+                    title_original: beatmap.metadata.title_original.clone(),
+                    mapper: beatmap.metadata.creator.clone(),
+                    difficulty: beatmap.metadata.difficulty.clone(),
+                },
+                stats: BmStats {
+                    ar: pp.ar,
+                    cs: pp.cs,
+                    od: pp.od,
+                    hp: pp.hp,
+                    sr: pp.stars,
+                    bpm: Bpm {
+                        min: pp.bpm_min,
+                        max: pp.bpm_max,
+                    },
+                    full_sr: pp.stars,
+                    memory_ar: stats.ar,
+                    memory_cs: stats.cs,
+                    memory_od: stats.od,
+                    memory_hp: stats.hp,
+                },
+                path: BmPath {
+                    full: location.get_file_path(),
+                    folder: location.folder.clone(),
+                    file: location.filename.clone(),
+                    bg: location.get_cover_path(),
+                    audio: location.get_audio_path(),
+                },
+            };
+        }
+
+        if let Some(gameplay) = &snapshot.gameplay {
+            state.gameplay = Gameplay {
+                game_mode: mode_id(mode),
+                name: gameplay.username.clone(),
+                score: gameplay.score,
+                accuracy: gameplay.accuracy,
+                combo: Combo {
+                    current: gameplay.combo,
+                    max: gameplay.max_combo,
+                },
+                hp: Hp {
+                    normal: gameplay.hp,
+                    smooth: gameplay.hp,
+                },
+                hits: GameplayHits {
+                    grade: Grade {
+                        current: gameplay.grade.to_string(),
+                        ..Default::default()
+                    },
+                    ..GameplayHits::new(&gameplay.hits)
+                },
+                pp: GameplayPp {
+                    current: pp.current,
+                    fc: pp.fc,
+                    max_this_play: pp.max_this_play,
+                },
+                ..Default::default()
+            };
+        }
+        if let Some(overlay) = &snapshot.key_overlay {
+            state.gameplay.key_overlay = KeyOverlay {
+                k1: overlay.key_1.into(),
+                k2: overlay.key_2.into(),
+                m1: overlay.mouse_1.into(),
+                m2: overlay.mouse_2.into(),
+            };
+        }
+
+        if let Some(result) = &snapshot.result_screen {
+            let hits = &result.hits;
+            state.results_screen = ResultsScreen {
+                name: result.username.clone(),
+                score: result.score,
+                max_combo: result.max_combo,
+                mods: Mods::new(result.mods),
+                _300: hits._300,
+                geki: hits._geki,
+                _100: hits._100,
+                katu: hits._katu,
+                _50: hits._50,
+                _0: hits._miss,
+            };
+        }
+        state
+    }
+}
+
+impl GameplayHits {
+    fn new(hits: &Hit) -> Self {
+        Self {
+            _300: hits._300,
+            geki: hits._geki,
+            _100: hits._100,
+            katu: hits._katu,
+            _50: hits._50,
+            _0: hits._miss,
+            ..Default::default()
+        }
+    }
+}
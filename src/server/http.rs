@@ -0,0 +1,209 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use crate::server::{Endpoint, Hub};
+use crate::Error;
+use tungstenite::Message;
+
+/// Largest request head accepted, overlays only ever send small GETs
+const MAX_HEAD: usize = 8 * 1024;
+
+struct Request {
+    path: String,
+    upgrade: bool,
+}
+
+/// Serves one connection, either a WebSocket client or a single file
+pub(crate) fn handle(stream: TcpStream, hub: &Hub, static_dir: Option<&Path>) -> Result<(), Error> {
+    let request = peek_request(&stream)?;
+    let (path, query) = request
+        .path
+        .split_once('?')
+        .unwrap_or((request.path.as_str(), ""));
+    let path = percent_decode(path);
+    let diff = query
+        .split('&')
+        .any(|param| matches!(param, "diff" | "diff=true" | "diff=1"));
+
+    if request.upgrade {
+        match Endpoint::from_websocket_path(&path) {
+            Some(endpoint) => serve_websocket(stream, hub, endpoint, diff),
+            None => respond(stream, "404 Not Found", "text/plain", b"Unknown endpoint"),
+        }
+    } else if let Some(endpoint) = Endpoint::from_json_path(&path) {
+        let json = endpoint.render(&hub.latest())?;
+        respond(stream, "200 OK", "application/json", json.as_bytes())
+    } else {
+        let file = match path.strip_prefix("/Songs/") {
+            Some(rest) => hub
+                .latest()
+                .songs_folder
+                .clone()
+                .and_then(|dir| safe_join(&dir, rest)),
+            None => static_dir.and_then(|dir| safe_join(dir, &path)),
+        };
+        serve_file(stream, file)
+    }
+}
+
+/// Reads the request head without consuming it so the WebSocket handshake
+/// can read it again
+fn peek_request(stream: &TcpStream) -> Result<Request, Error> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut buffer = vec![0u8; MAX_HEAD];
+    let head = loop {
+        let read = stream.peek(&mut buffer)?;
+        if read == 0 {
+            return Err(Error::Other(
+                "Connection closed before the request".to_string(),
+            ));
+        }
+        if let Some(end) = buffer[..read].windows(4).position(|w| w == b"\r\n\r\n") {
+            break String::from_utf8_lossy(&buffer[..end]).into_owned();
+        }
+        if read == buffer.len() {
+            return Err(Error::Parse("Request head is too large".to_string()));
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    };
+
+    let mut lines = head.lines();
+    let path = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .ok_or_else(|| Error::Parse(format!("Invalid request line in {head:?}")))?
+        .to_string();
+    let upgrade = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("upgrade")
+                && value.trim().eq_ignore_ascii_case("websocket")
+        })
+    });
+    Ok(Request { path, upgrade })
+}
+
+fn serve_websocket(
+    stream: TcpStream,
+    hub: &Hub,
+    endpoint: Endpoint,
+    diff: bool,
+) -> Result<(), Error> {
+    let mut socket = tungstenite::accept(stream).map_err(|e| Error::Other(e.to_string()))?;
+    // Incoming messages are only drained to answer pings and notice closes
+    socket
+        .get_mut()
+        .set_read_timeout(Some(Duration::from_millis(1)))?;
+
+    let messages = hub.subscribe(endpoint, diff);
+    tracing::debug!(path = endpoint.websocket_path(), "client connected");
+    while let Ok(message) = messages.recv() {
+        socket
+            .send(Message::text(message.as_ref()))
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        match socket.read() {
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(e) => return Err(Error::Other(e.to_string())),
+        }
+    }
+    Ok(())
+}
+
+fn serve_file(stream: TcpStream, path: Option<PathBuf>) -> Result<(), Error> {
+    let path = path.map(|path| {
+        if path.is_dir() {
+            path.join("index.html")
+        } else {
+            path
+        }
+    });
+    match path.and_then(|path| std::fs::read(&path).ok().map(|body| (path, body))) {
+        Some((path, body)) => respond(stream, "200 OK", content_type(&path), &body),
+        None => respond(stream, "404 Not Found", "text/plain", b"Not found"),
+    }
+}
+
+fn respond(
+    mut stream: TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> Result<(), Error> {
+    // Drain the request so closing the socket doesn't reset the connection
+    let mut head = [0u8; MAX_HEAD];
+    stream.set_read_timeout(Some(Duration::from_millis(10)))?;
+    let _ = stream.read(&mut head);
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// `base` joined with the url path `path`, `None` if it would escape `base`
+fn safe_join(base: &Path, path: &str) -> Option<PathBuf> {
+    let relative = Path::new(path.trim_start_matches('/'));
+    relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+        .then(|| base.join(relative))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn content_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "json" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        _ => "application/octet-stream",
+    }
+}
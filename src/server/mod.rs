@@ -0,0 +1,367 @@
+//! Local server broadcasting osu! state to stream overlays.
+//!
+//! A single polling loop reads an [`OsuSnapshot`] every tick and each
+//! connected WebSocket client receives it rendered in the format of the
+//! endpoint it connected to:
+//!
+//! - `/ws`: gosumemory, see [`gosumemory`]
+//! - `/websocket/v2`: tosu v2, see [`tosu`]
+//!
+//! Clients connecting with `?diff=true` (`/ws?diff=true`) only get the first
+//! message whole, every following one is a JSON Patch from the previous one
+//! (see [`crate::reader::diff`]) and nothing is sent when nothing changed.
+//!
+//! The latest message of each endpoint is also available over plain HTTP at
+//! `/json` and `/json/v2`. Other HTTP requests are answered with files from
+//! the static folder (overlays) and `/Songs/...` from the osu! songs folder
+//! (backgrounds).
+pub mod gosumemory;
+mod http;
+mod pp;
+pub mod tosu;
+
+use std::collections::{HashMap, HashSet};
+use std::net::{SocketAddr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::memory::MemorySource;
+use crate::reader::common::{GameMode, GameState};
+use crate::reader::diff::diff;
+use crate::reader::snapshot::OsuSnapshot;
+use crate::reader::supervisor::Supervisor;
+use crate::Error;
+use serde_json::Value;
+
+pub use pp::PpInfo;
+
+/// Everything endpoints render from, produced once per tick
+#[derive(Debug, Clone, Default)]
+pub struct Tick {
+    /// Whether osu! is running, the rest is default when it is not
+    pub attached: bool,
+    pub snapshot: OsuSnapshot,
+    /// Folder containing osu!.exe
+    pub game_folder: Option<PathBuf>,
+    pub songs_folder: Option<PathBuf>,
+    pub pp: PpInfo,
+}
+
+impl Tick {
+    /// Mode being played or shown, the ruleset picked in song select also
+    /// covers converts
+    pub fn mode(&self) -> GameMode {
+        let snapshot = &self.snapshot;
+        snapshot
+            .result_screen
+            .as_ref()
+            .map(|result| result.mode)
+            .or(snapshot.menu_ruleset)
+            .or(snapshot
+                .beatmap
+                .as_ref()
+                .map(|beatmap| beatmap.technical.mode))
+            .unwrap_or_default()
+    }
+}
+
+/// Message format a client asked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Gosumemory,
+    TosuV2,
+}
+
+impl Endpoint {
+    pub const ALL: [Endpoint; 2] = [Endpoint::Gosumemory, Endpoint::TosuV2];
+
+    pub fn websocket_path(&self) -> &'static str {
+        match self {
+            Endpoint::Gosumemory => "/ws",
+            Endpoint::TosuV2 => "/websocket/v2",
+        }
+    }
+
+    pub fn json_path(&self) -> &'static str {
+        match self {
+            Endpoint::Gosumemory => "/json",
+            Endpoint::TosuV2 => "/json/v2",
+        }
+    }
+
+    pub fn from_websocket_path(path: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|endpoint| endpoint.websocket_path() == path)
+    }
+
+    pub fn from_json_path(path: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|endpoint| endpoint.json_path() == path)
+    }
+
+    /// JSON message sent to clients of this endpoint
+    pub fn render(&self, tick: &Tick) -> Result<String, Error> {
+        serde_json::to_string(&self.render_value(tick)?).map_err(|e| Error::Parse(e.to_string()))
+    }
+
+    pub fn render_value(&self, tick: &Tick) -> Result<Value, Error> {
+        let value = match self {
+            Endpoint::Gosumemory => serde_json::to_value(gosumemory::GosumemoryState::new(tick)),
+            Endpoint::TosuV2 => serde_json::to_value(tosu::TosuState::new(tick)),
+        };
+        value.map_err(|e| Error::Parse(e.to_string()))
+    }
+}
+
+struct Client {
+    endpoint: Endpoint,
+    /// Wants patches instead of full messages after the first one
+    diff: bool,
+    tx: Sender<Arc<str>>,
+}
+
+/// One tick of an endpoint
+struct Rendered {
+    full: Arc<str>,
+    /// `None` when nothing changed
+    patch: Option<Arc<str>>,
+}
+
+#[derive(Default)]
+struct HubState {
+    clients: Vec<Client>,
+    latest: Arc<Tick>,
+    /// Last message of every endpoint in use, what patches are computed from
+    previous: HashMap<Endpoint, Value>,
+}
+
+/// Shared between the polling loop and the connection threads
+#[derive(Default)]
+pub(crate) struct Hub {
+    state: Mutex<HubState>,
+}
+
+impl Hub {
+    pub(crate) fn subscribe(&self, endpoint: Endpoint, diff: bool) -> Receiver<Arc<str>> {
+        let (tx, rx) = mpsc::channel();
+        let mut state = lock(&self.state);
+        // New clients get the current state right away instead of on the next
+        // tick, it is also the base of their first patch
+        if let Ok(value) = endpoint.render_value(&state.latest) {
+            let _ = tx.send(value.to_string().into());
+            state.previous.insert(endpoint, value);
+        }
+        state.clients.push(Client { endpoint, diff, tx });
+        rx
+    }
+
+    pub(crate) fn latest(&self) -> Arc<Tick> {
+        lock(&self.state).latest.clone()
+    }
+
+    /// Renders `tick` once per endpoint in use and sends it to every client,
+    /// either whole or as a patch from the previous tick
+    fn broadcast(&self, tick: Tick) {
+        let mut state = lock(&self.state);
+        let state = &mut *state;
+        state.latest = Arc::new(tick);
+
+        let mut messages: HashMap<Endpoint, Option<Rendered>> = HashMap::new();
+        let wants_patch: HashSet<Endpoint> = state
+            .clients
+            .iter()
+            .filter(|client| client.diff)
+            .map(|client| client.endpoint)
+            .collect();
+        state.clients.retain(|client| {
+            let message = messages.entry(client.endpoint).or_insert_with(|| {
+                let value = client
+                    .endpoint
+                    .render_value(&state.latest)
+                    .inspect_err(|e| tracing::warn!(error = %e, "failed to render state"))
+                    .ok()?;
+                let patch = wants_patch
+                    .contains(&client.endpoint)
+                    .then(|| state.previous.get(&client.endpoint))
+                    .flatten()
+                    .map(|previous| diff(previous, &value))
+                    .filter(|patch| !patch.is_empty())
+                    .and_then(|patch| serde_json::to_string(&patch).ok())
+                    .map(Arc::from);
+                let full = Arc::from(value.to_string());
+                state.previous.insert(client.endpoint, value);
+                Some(Rendered { full, patch })
+            });
+            let message = match message {
+                Some(rendered) if client.diff => rendered.patch.as_ref(),
+                Some(rendered) => Some(&rendered.full),
+                None => None,
+            };
+            // Nothing to send when rendering failed or, for diff clients, nothing changed
+            message.is_none_or(|message| client.tx.send(message.clone()).is_ok())
+        });
+    }
+}
+
+/// osu!'s ruleset id
+pub(crate) fn mode_id(mode: GameMode) -> u32 {
+    match mode {
+        GameMode::Taiko => 1,
+        GameMode::Catch => 2,
+        GameMode::Mania => 3,
+        GameMode::Osu | GameMode::Unknown => 0,
+    }
+}
+
+/// osu!'s raw game state, `Unknown` is 10 like in memory
+pub(crate) fn game_state_id(state: GameState) -> u32 {
+    match state {
+        GameState::Unknown => 10,
+        state => state as u32,
+    }
+}
+
+pub(crate) fn display(path: Option<&Path>) -> String {
+    path.map(|path| path.display().to_string())
+        .unwrap_or_default()
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Reads a [`Tick`] from osu!, reattaching through a [`Supervisor`]
+pub struct Poller<P: MemorySource> {
+    supervisor: Supervisor<P>,
+    snapshot: OsuSnapshot,
+    pp: pp::PpCalculator,
+}
+
+impl<P: MemorySource> Poller<P> {
+    pub fn new(supervisor: Supervisor<P>) -> Self {
+        Self {
+            supervisor,
+            snapshot: OsuSnapshot::default(),
+            pp: pp::PpCalculator::default(),
+        }
+    }
+
+    pub fn poll(&mut self) -> Tick {
+        let snapshot = &mut self.snapshot;
+        let read = self.supervisor.read(|session| {
+            session.refresh(snapshot)?;
+            Ok((
+                session.process().executable_dir(),
+                session.common().path_folder().ok(),
+            ))
+        });
+
+        match read {
+            Ok((game_folder, songs_folder)) => {
+                let pp = self.pp.update(&self.snapshot, songs_folder.as_deref());
+                Tick {
+                    attached: true,
+                    snapshot: self.snapshot.clone(),
+                    game_folder,
+                    songs_folder,
+                    pp,
+                }
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "poll failed");
+                self.snapshot = OsuSnapshot::default();
+                Tick::default()
+            }
+        }
+    }
+}
+
+/// Overlay server, see the [module documentation](self).
+///
+/// # Examples
+///
+/// ```no_run
+/// use rosu_memory_lib::server::Server;
+///
+/// // Serves gosumemory overlays from ./static at http://127.0.0.1:24050
+/// Server::new().static_dir("static").run()?;
+/// # Ok::<(), rosu_memory_lib::Error>(())
+/// ```
+pub struct Server {
+    address: SocketAddr,
+    interval: Duration,
+    static_dir: Option<PathBuf>,
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Server {
+    /// Listens on `127.0.0.1:24050` like gosumemory and polls every 100ms
+    pub fn new() -> Self {
+        Self {
+            address: SocketAddr::from(([127, 0, 0, 1], 24050)),
+            interval: Duration::from_millis(100),
+            static_dir: None,
+        }
+    }
+
+    pub fn address(mut self, address: impl Into<SocketAddr>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// Time between two broadcasts
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Folder served over HTTP, usually gosumemory's `static` folder with one
+    /// overlay per subfolder
+    pub fn static_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.static_dir = Some(dir.into());
+        self
+    }
+
+    /// Serves forever, attaching to osu!.exe whenever it runs
+    pub fn run(self) -> Result<(), Error> {
+        self.run_with(Poller::new(Supervisor::new()))
+    }
+
+    /// Serves forever using `poller` as the source of data
+    pub fn run_with<P: MemorySource>(self, mut poller: Poller<P>) -> Result<(), Error> {
+        let listener = TcpListener::bind(self.address)?;
+        tracing::info!(address = %self.address, "listening");
+
+        let hub = Arc::new(Hub::default());
+        let static_dir = Arc::new(self.static_dir);
+        {
+            let hub = hub.clone();
+            std::thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let hub = hub.clone();
+                    let static_dir = static_dir.clone();
+                    std::thread::spawn(move || {
+                        if let Err(e) = http::handle(stream, &hub, static_dir.as_deref()) {
+                            tracing::debug!(error = %e, "connection closed");
+                        }
+                    });
+                }
+            });
+        }
+
+        loop {
+            let started = Instant::now();
+            hub.broadcast(poller.poll());
+            std::thread::sleep(self.interval.saturating_sub(started.elapsed()));
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::path::Path;
+
+use crate::reader::beatmap::common::BeatmapInfo;
+use crate::reader::beatmap::difficulty::{as_played, modded_stats_of, DifficultyMods};
+use crate::reader::common::{GameMode, GameState};
+use crate::reader::pp::LivePp;
+use crate::reader::snapshot::OsuSnapshot;
+use rosu_mods::GameModsLegacy;
+use rosu_pp::model::hit_object::HitObject;
+use rosu_pp::{Beatmap, Difficulty, Performance};
+
+/// Accuracies of [`PpInfo::accuracy`], in the same order
+pub const PP_ACCURACIES: [f64; 6] = [100.0, 99.0, 98.0, 97.0, 96.0, 95.0];
+
+/// Values computed from the beatmap file with rosu-pp for the active mods
+#[derive(Debug, Clone, Default)]
+pub struct PpInfo {
+    pub mods: GameModsLegacy,
+    pub clock_rate: f64,
+    pub stars: f64,
+    pub max_combo: u32,
+    pub ar: f64,
+    pub cs: f64,
+    pub od: f64,
+    pub hp: f64,
+    /// Most common bpm
+    pub bpm: f64,
+    pub bpm_min: f64,
+    pub bpm_max: f64,
+    pub circles: u32,
+    pub sliders: u32,
+    pub spinners: u32,
+    /// osu!mania hold notes
+    pub holds: u32,
+    /// Start time of the first and last hit objects in milliseconds
+    pub first_object: f64,
+    pub last_object: f64,
+    /// pp for every accuracy of [`PP_ACCURACIES`]
+    pub accuracy: [f64; 6],
+    /// pp of the play so far, only while playing
+    pub current: f64,
+    /// pp if misses are turned into perfect hits and the rest of the play is
+    /// hit perfectly
+    pub fc: f64,
+    /// Highest `current` reached during this play
+    pub max_this_play: f64,
+}
+
+/// Keeps the parsed beatmap and the values depending only on (md5, mode,
+/// mods) between ticks, the live values come from [`LivePp`]
+#[derive(Default)]
+pub(crate) struct PpCalculator {
+    /// (md5, mode, mods) of `info`
+    key: Option<(String, GameMode, GameModsLegacy)>,
+    map: Option<Beatmap>,
+    info: PpInfo,
+    live: LivePp,
+}
+
+impl PpCalculator {
+    pub(crate) fn update(&mut self, snapshot: &OsuSnapshot, songs_folder: Option<&Path>) -> PpInfo {
+        let Some(beatmap) = &snapshot.beatmap else {
+            return PpInfo::default();
+        };
+        let (mode, mods) = match &snapshot.gameplay {
+            Some(gameplay) => (gameplay.mode, gameplay.mods),
+            None => (
+                snapshot.menu_ruleset.unwrap_or(beatmap.technical.mode),
+                snapshot.menu_mods.unwrap_or_default(),
+            ),
+        };
+
+        let key = (beatmap.technical.md5.clone(), mode, mods);
+        // a beatmap that couldn't be loaded is tried again every tick, the
+        // songs folder or the file can show up later (e.g. while downloading)
+        let retry = self.map.is_none();
+        if retry || self.key.as_ref() != Some(&key) {
+            let changed = self.key.as_ref().is_none_or(|(md5, ..)| *md5 != key.0);
+            if changed || retry {
+                self.map = songs_folder.and_then(|folder| load(folder, beatmap, changed));
+            }
+            self.key = Some(key);
+            self.info = self.static_info(mode, mods);
+        }
+
+        let live = match (&snapshot.gameplay, songs_folder) {
+            (Some(gameplay), Some(folder)) if snapshot.game_state == GameState::Playing => {
+                let path = folder
+                    .join(&beatmap.location.folder)
+                    .join(&beatmap.location.filename);
+                self.live
+                    .set_beatmap(path)
+                    .and_then(|()| self.live.update(gameplay))
+                    .inspect_err(|e| tracing::debug!(error = %e, "live pp unavailable"))
+                    .ok()
+            }
+            _ => None,
+        };
+        match live {
+            Some(live) => {
+                // A retry starts from zero hits
+                if live.current == 0.0 {
+                    self.info.max_this_play = 0.0;
+                }
+                self.info.current = live.current;
+                self.info.fc = live.if_fc;
+                self.info.max_this_play = self.info.max_this_play.max(live.current);
+            }
+            None => {
+                self.live.reset();
+                self.info.current = 0.0;
+                self.info.fc = 0.0;
+                self.info.max_this_play = 0.0;
+            }
+        }
+        self.info.clone()
+    }
+
+    fn static_info(&self, mode: GameMode, mods: GameModsLegacy) -> PpInfo {
+        let Some(map) = &self.map else {
+            return PpInfo {
+                mods,
+                clock_rate: mods.clock_rate(),
+                ..Default::default()
+            };
+        };
+        let map = as_played(map, mode, mods);
+        let difficulty = Difficulty::new().mods(mods).calculate(map.as_ref());
+        let stats = modded_stats_of(&map, mode, &DifficultyMods::from(mods));
+
+        let count = |kind: fn(&HitObject) -> bool| {
+            map.hit_objects.iter().filter(|h| kind(h)).count() as u32
+        };
+
+        PpInfo {
+            mods,
+            clock_rate: stats.clock_rate,
+            stars: difficulty.stars(),
+            max_combo: difficulty.max_combo(),
+            ar: stats.ar,
+            cs: stats.cs,
+            od: stats.od,
+            hp: stats.hp,
+            bpm: stats.bpm,
+            bpm_min: stats.bpm_min,
+            bpm_max: stats.bpm_max,
+            circles: count(HitObject::is_circle),
+            sliders: count(HitObject::is_slider),
+            spinners: count(HitObject::is_spinner),
+            holds: count(HitObject::is_hold_note),
+            first_object: map.hit_objects.first().map_or(0.0, |h| h.start_time),
+            last_object: map.hit_objects.last().map_or(0.0, |h| h.start_time),
+            accuracy: PP_ACCURACIES.map(|accuracy| {
+                Performance::new(difficulty.clone())
+                    .mods(mods)
+                    .lazer(false)
+                    .accuracy(accuracy)
+                    .calculate()
+                    .pp()
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+/// Parses the beatmap file, only the first failure for a beatmap is a warning
+fn load(songs_folder: &Path, beatmap: &BeatmapInfo, first_try: bool) -> Option<Beatmap> {
+    let path = songs_folder
+        .join(&beatmap.location.folder)
+        .join(&beatmap.location.filename);
+    Beatmap::from_path(&path)
+        .inspect_err(|e| match first_try {
+            true => tracing::warn!(error = %e, path = %path.display(), "cannot parse beatmap"),
+            false => tracing::debug!(error = %e, path = %path.display(), "cannot parse beatmap"),
+        })
+        .ok()
+}
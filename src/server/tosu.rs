@@ -0,0 +1,564 @@
+//! tosu v2 `/websocket/v2` and `/json/v2` message.
+//!
+//! Field names follow tosu's v2 JSON so overlays written for tosu work
+//! without changes. Enums are sent as `{ "number", "name" }` pairs like tosu
+//! does, values we can't read (settings, leaderboard, graphs...) are left out
+//! or sent with their empty defaults.
+use crate::reader::beatmap::common::BeatmapStatus;
+use crate::reader::common::{GameMode, GameState};
+use crate::reader::structs::Hit;
+use crate::server::{display, game_state_id, mode_id, PpInfo, Tick};
+use rosu_mods::GameModsLegacy;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TosuState {
+    pub state: NumberName,
+    pub profile: Profile,
+    pub beatmap: Beatmap,
+    pub play: Play,
+    pub performance: Performance,
+    pub results_screen: ResultsScreen,
+    pub folders: Folders,
+    pub files: Files,
+    pub direct_path: DirectPath,
+}
+
+/// Enum value as tosu sends it
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NumberName {
+    pub number: u32,
+    pub name: &'static str,
+}
+
+impl From<GameState> for NumberName {
+    fn from(state: GameState) -> Self {
+        let name = match state {
+            GameState::MainMenu => "menu",
+            GameState::Editor => "edit",
+            GameState::Playing => "play",
+            GameState::Exit => "exit",
+            GameState::EditorSongSelect => "selectEdit",
+            GameState::SongSelect => "selectPlay",
+            GameState::SelectDrawing => "selectDrawings",
+            GameState::ResultScreen => "resultScreen",
+            GameState::Update => "update",
+            GameState::Busy => "busy",
+            GameState::MultiplayerLobbySelect => "lobby",
+            GameState::MultiplayerLobby => "matchSetup",
+            GameState::MultiplayerSongSelect => "selectMulti",
+            GameState::MultiplayerResultScreen => "rankingVs",
+            GameState::OffsetWizard => "optionsOffsetWizard",
+            GameState::MultiplayerResultScreenTagCoop => "rankingTagCoop",
+            GameState::MultiplayerResultScreenTeamVs => "rankingTeam",
+            GameState::SongImport => "beatmapImport",
+            GameState::Unknown => "unknown",
+        };
+        Self {
+            number: game_state_id(state),
+            name,
+        }
+    }
+}
+
+impl From<GameMode> for NumberName {
+    fn from(mode: GameMode) -> Self {
+        let name = match mode {
+            GameMode::Osu | GameMode::Unknown => "osu",
+            GameMode::Taiko => "taiko",
+            GameMode::Catch => "fruits",
+            GameMode::Mania => "mania",
+        };
+        Self {
+            number: mode_id(mode),
+            name,
+        }
+    }
+}
+
+impl From<BeatmapStatus> for NumberName {
+    fn from(status: BeatmapStatus) -> Self {
+        let name = match status {
+            BeatmapStatus::Unknown | BeatmapStatus::Unused => "unknown",
+            BeatmapStatus::Unsubmitted => "notSubmitted",
+            BeatmapStatus::Unranked => "pending",
+            BeatmapStatus::Ranked => "ranked",
+            BeatmapStatus::Approved => "approved",
+            BeatmapStatus::Qualified => "qualified",
+            BeatmapStatus::Loved => "loved",
+        };
+        Self {
+            number: status as u32,
+            name,
+        }
+    }
+}
+
+/// Online status of the logged in user
+fn bancho_status(status: i32) -> NumberName {
+    const NAMES: [&str; 14] = [
+        "idle",
+        "afk",
+        "playing",
+        "editing",
+        "modding",
+        "multiplayer",
+        "watching",
+        "unknown",
+        "testing",
+        "submitting",
+        "paused",
+        "lobby",
+        "multiplaying",
+        "osuDirect",
+    ];
+    NumberName {
+        number: status.max(0) as u32,
+        name: usize::try_from(status)
+            .ok()
+            .and_then(|i| NAMES.get(i))
+            .unwrap_or(&"unknown"),
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub bancho_status: NumberName,
+    pub id: i32,
+    pub name: String,
+    pub mode: NumberName,
+    pub ranked_score: i64,
+    pub level: f32,
+    pub accuracy: f64,
+    pub pp: i32,
+    pub play_count: i32,
+    pub global_rank: i32,
+    pub country_code: NumberName,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Beatmap {
+    pub time: BeatmapTime,
+    pub status: NumberName,
+    pub checksum: String,
+    pub id: i32,
+    pub set: i32,
+    pub mode: NumberName,
+    pub artist: String,
+    pub artist_unicode: String,
+    pub title: String,
+    pub title_unicode: String,
+    pub mapper: String,
+    pub version: String,
+    pub stats: BeatmapStats,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BeatmapTime {
+    pub live: i32,
+    pub first_object: f64,
+    pub last_object: f64,
+    pub mp3_length: i32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BeatmapStats {
+    pub stars: Stars,
+    pub ar: Attribute,
+    pub cs: Attribute,
+    pub od: Attribute,
+    pub hp: Attribute,
+    pub bpm: Bpm,
+    pub objects: Objects,
+    pub max_combo: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Stars {
+    pub live: f64,
+    pub total: f64,
+}
+
+/// Value from the beatmap and value with mods applied
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Attribute {
+    pub original: f32,
+    pub converted: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Bpm {
+    pub common: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Objects {
+    pub circles: u32,
+    pub sliders: u32,
+    pub spinners: u32,
+    pub holds: u32,
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Play {
+    pub player_name: String,
+    pub mode: NumberName,
+    pub score: i32,
+    pub accuracy: f64,
+    pub health_bar: HealthBar,
+    pub hits: Hits,
+    pub hit_error_array: Vec<i32>,
+    pub combo: Combo,
+    pub mods: Mods,
+    pub rank: Rank,
+    pub pp: PlayPp,
+    pub unstable_rate: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HealthBar {
+    pub normal: f64,
+    pub smooth: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hits {
+    #[serde(rename = "0")]
+    pub _0: i16,
+    #[serde(rename = "50")]
+    pub _50: i16,
+    #[serde(rename = "100")]
+    pub _100: i16,
+    #[serde(rename = "300")]
+    pub _300: i16,
+    pub geki: i16,
+    pub katu: i16,
+    pub slider_breaks: i16,
+}
+
+impl From<&Hit> for Hits {
+    fn from(hits: &Hit) -> Self {
+        Self {
+            _0: hits._miss,
+            _50: hits._50,
+            _100: hits._100,
+            _300: hits._300,
+            geki: hits._geki,
+            katu: hits._katu,
+            slider_breaks: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Combo {
+    pub current: i16,
+    pub max: i16,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Mods {
+    pub number: u32,
+    pub name: String,
+    pub array: Vec<Acronym>,
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Acronym {
+    pub acronym: String,
+}
+
+impl Mods {
+    fn new(mods: GameModsLegacy) -> Self {
+        if mods.is_empty() {
+            return Self {
+                rate: 1.0,
+                ..Default::default()
+            };
+        }
+        Self {
+            number: mods.bits(),
+            name: mods.to_string(),
+            array: mods
+                .iter()
+                .map(|m| Acronym {
+                    acronym: m.to_string(),
+                })
+                .collect(),
+            rate: mods.clock_rate(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rank {
+    pub current: String,
+    pub max_this_play: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayPp {
+    pub current: f64,
+    pub fc: f64,
+    pub max_achieved: f64,
+    pub max_achievable: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Performance {
+    pub accuracy: AccuracyPp,
+}
+
+/// pp for SS and 99 to 95% accuracy
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AccuracyPp {
+    #[serde(rename = "95")]
+    pub _95: f64,
+    #[serde(rename = "96")]
+    pub _96: f64,
+    #[serde(rename = "97")]
+    pub _97: f64,
+    #[serde(rename = "98")]
+    pub _98: f64,
+    #[serde(rename = "99")]
+    pub _99: f64,
+    #[serde(rename = "100")]
+    pub _100: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultsScreen {
+    pub player_name: String,
+    pub mode: NumberName,
+    pub score: i32,
+    pub accuracy: f64,
+    pub hits: Hits,
+    pub mods: Mods,
+    pub max_combo: i16,
+    pub rank: String,
+    pub pp: ResultsPp,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResultsPp {
+    pub current: f64,
+    pub fc: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Folders {
+    pub game: String,
+    pub skin: String,
+    pub songs: String,
+    pub beatmap: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Files {
+    pub beatmap: String,
+    pub background: String,
+    pub audio: String,
+}
+
+/// Absolute paths
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectPath {
+    pub beatmap_file: String,
+    pub beatmap_background: String,
+    pub audio: String,
+    pub beatmap_folder: String,
+    pub skin_folder: String,
+}
+
+impl TosuState {
+    pub fn new(tick: &Tick) -> Self {
+        let snapshot = &tick.snapshot;
+        let pp = &tick.pp;
+        let mode = tick.mode();
+
+        let mut state = Self {
+            state: if tick.attached {
+                snapshot.game_state.into()
+            } else {
+                GameState::Unknown.into()
+            },
+            performance: Performance {
+                accuracy: accuracy_pp(pp),
+            },
+            ..Default::default()
+        };
+
+        if let Some(user) = &snapshot.user {
+            state.profile = Profile {
+                bancho_status: bancho_status(user.bancho_status),
+                id: user.id,
+                name: user.username.clone(),
+                mode: GameMode::from(user.playmode).into(),
+                ranked_score: user.rankedscore,
+                level: user.level,
+                accuracy: user.accuracy,
+                pp: user.pp,
+                play_count: user.playcount,
+                global_rank: user.rank,
+                country_code: NumberName {
+                    number: user.country_code.max(0) as u32,
+                    name: "",
+                },
+            };
+        }
+
+        if let Some(beatmap) = &snapshot.beatmap {
+            let stats = &beatmap.stats;
+            let attribute = |original: f32, converted: f64| Attribute {
+                original,
+                converted: if pp.stars > 0.0 {
+                    converted
+                } else {
+                    original.into()
+                },
+            };
+            state.beatmap = Beatmap {
+                time: BeatmapTime {
+                    live: snapshot.game_time.unwrap_or_default(),
+                    first_object: pp.first_object,
+                    last_object: pp.last_object,
+                    mp3_length: stats.length,
+                },
+                status: beatmap.technical.ranked_status.into(),
+                checksum: beatmap.technical.md5.clone(),
+                id: beatmap.technical.id,
+                set: beatmap.technical.set_id,
+                mode: beatmap.technical.mode.into(),
+                artist: beatmap.metadata.author.clone(),
+                artist_unicode: beatmap.metadata.author.clone(),
+                title: beatmap.metadata.title_romanized.clone(),
+                title_unicode: beatmap.metadata.title_original.clone(),
+                mapper: beatmap.metadata.creator.clone(),
+                version: beatmap.metadata.difficulty.clone(),
+                stats: BeatmapStats {
+                    stars: Stars {
+                        live: pp.stars,
+                        total: pp.stars,
+                    },
+                    ar: attribute(stats.ar, pp.ar),
+                    cs: attribute(stats.cs, pp.cs),
+                    od: attribute(stats.od, pp.od),
+                    hp: attribute(stats.hp, pp.hp),
+                    bpm: Bpm {
+                        common: pp.bpm,
+                        min: pp.bpm_min,
+                        max: pp.bpm_max,
+                    },
+                    objects: Objects {
+                        circles: pp.circles,
+                        sliders: pp.sliders,
+                        spinners: pp.spinners,
+                        holds: pp.holds,
+                        total: stats.object_count.max(0) as u32,
+                    },
+                    max_combo: pp.max_combo,
+                },
+            };
+
+            let songs = tick.songs_folder.as_deref();
+            let location = &beatmap.location;
+            let absolute = |file: &str| {
+                display(
+                    songs
+                        .map(|songs| songs.join(&location.folder).join(file))
+                        .as_deref(),
+                )
+            };
+            state.folders.beatmap = location.folder.clone();
+            state.files = Files {
+                beatmap: location.filename.clone(),
+                background: location.cover.clone(),
+                audio: location.audio.clone(),
+            };
+            state.direct_path = DirectPath {
+                beatmap_file: absolute(&location.filename),
+                beatmap_background: absolute(&location.cover),
+                audio: absolute(&location.audio),
+                beatmap_folder: absolute(""),
+                skin_folder: String::new(),
+            };
+        }
+        state.folders.game = display(tick.game_folder.as_deref());
+        state.folders.songs = display(tick.songs_folder.as_deref());
+
+        if let Some(gameplay) = &snapshot.gameplay {
+            state.play = Play {
+                player_name: gameplay.username.clone(),
+                mode: mode.into(),
+                score: gameplay.score,
+                accuracy: gameplay.accuracy,
+                rank: Rank {
+                    current: gameplay.grade.to_string(),
+                    ..Default::default()
+                },
+                health_bar: HealthBar {
+                    normal: gameplay.hp,
+                    smooth: gameplay.hp,
+                },
+                hits: (&gameplay.hits).into(),
+                combo: Combo {
+                    current: gameplay.combo,
+                    max: gameplay.max_combo,
+                },
+                mods: Mods::new(gameplay.mods),
+                pp: PlayPp {
+                    current: pp.current,
+                    fc: pp.fc,
+                    max_achieved: pp.max_this_play,
+                    max_achievable: pp.accuracy[0],
+                },
+                ..Default::default()
+            };
+        }
+
+        if let Some(result) = &snapshot.result_screen {
+            state.results_screen = ResultsScreen {
+                player_name: result.username.clone(),
+                mode: result.mode.into(),
+                score: result.score,
+                accuracy: result.accuracy,
+                hits: (&result.hits).into(),
+                mods: Mods::new(result.mods),
+                max_combo: result.max_combo,
+                rank: result.grade.to_string(),
+                pp: ResultsPp {
+                    current: result.pp.unwrap_or_default(),
+                    ..Default::default()
+                },
+            };
+        }
+        state
+    }
+}
+
+fn accuracy_pp(pp: &PpInfo) -> AccuracyPp {
+    let [ss, acc99, acc98, acc97, acc96, acc95] = pp.accuracy;
+    AccuracyPp {
+        _95: acc95,
+        _96: acc96,
+        _97: acc97,
+        _98: acc98,
+        _99: acc99,
+        _100: ss,
+    }
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "testing")]
+
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::beatmap::stable::offset::BEATMAP_OFFSET;
+use rosu_memory_lib::reader::beatmap::BeatmapReader;
+use rosu_memory_lib::reader::common::stable::offset::COMMON_OFFSET;
+use rosu_memory_lib::reader::common::{GameMode, GameState, OsuClientKind};
+use rosu_memory_lib::reader::offsets::{set_offsets, OffsetTable};
+use rosu_memory_lib::reader::structs::{State, StaticAddresses};
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+Mode: 1
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,1500,1,0,0:0:0:0:
+";
+
+#[test]
+fn stats_and_mode_follow_the_offset_table() {
+    // stats laid out apart from each other, nothing can be read as one block
+    let mut table = OffsetTable::default();
+    table.beatmap.stats.ar = 0x1a0;
+    table.beatmap.stats.cs = 0x1b0;
+    table.beatmap.stats.hp = 0x1c0;
+    table.beatmap.stats.od = 0x1d0;
+    set_offsets(table).unwrap();
+
+    let dir = std::env::temp_dir().join(format!("rosu-memory-lib-beatmap-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("Songs").join("1 Test")).unwrap();
+    std::fs::write(dir.join("Songs").join("1 Test").join("test.osu"), OSU_FILE).unwrap();
+
+    let mut fake = FakeProcess::new().with_executable_dir(&dir);
+    let addresses = fake.place_signatures();
+    let status = fake.pointer_chain(addresses.status, &[-COMMON_OFFSET.status]);
+    fake.write_u32(status, GameState::SongSelect as u32);
+    fake.pointer_chain(addresses.menu_mods, &[COMMON_OFFSET.mods_ptr]);
+    let settings = fake.pointer_chain(
+        addresses.settings,
+        &[COMMON_OFFSET.settings_ptr, COMMON_OFFSET.settings_addr],
+    );
+    fake.write_string(settings + COMMON_OFFSET.path, "Songs");
+
+    let beatmap = fake.pointer_chain(addresses.base, &[-BEATMAP_OFFSET.ptr, 0]);
+    let metadata = &BEATMAP_OFFSET.metadata;
+    for offset in [
+        metadata.author,
+        metadata.creator,
+        metadata.title_romanized,
+        metadata.title_original,
+        metadata.difficulty,
+        metadata.tags,
+        BEATMAP_OFFSET.location.audio,
+        BEATMAP_OFFSET.location.cover,
+    ] {
+        fake.write_string(beatmap + offset, "");
+    }
+    fake.write_string(beatmap + BEATMAP_OFFSET.technical.md5, "d41d8cd9");
+    fake.write_string(beatmap + BEATMAP_OFFSET.location.folder, "1 Test");
+    fake.write_string(beatmap + BEATMAP_OFFSET.location.filename, "test.osu");
+    fake.write_i32(beatmap + BEATMAP_OFFSET.technical.mode, 1);
+    fake.write_f32(beatmap + 0x1a0, 9.0);
+    fake.write_f32(beatmap + 0x1b0, 4.0);
+    fake.write_f32(beatmap + 0x1c0, 5.0);
+    fake.write_f32(beatmap + 0x1d0, 8.0);
+
+    let mut state = State {
+        addresses: StaticAddresses::new(&fake).unwrap(),
+    };
+    let mut reader = BeatmapReader::new(&fake, &mut state, OsuClientKind::Stable).unwrap();
+    let stats = reader.stats();
+    let info = reader.info();
+    set_offsets(OffsetTable::default()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let info = info.unwrap();
+    for stats in [stats.unwrap(), info.stats] {
+        assert_eq!(
+            (stats.ar, stats.cs, stats.hp, stats.od),
+            (9.0, 4.0, 5.0, 8.0)
+        );
+    }
+    assert_eq!(info.technical.mode, GameMode::Taiko);
+}
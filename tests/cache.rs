@@ -0,0 +1,64 @@
+#![cfg(feature = "testing")]
+
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::cache::SignatureCache;
+use rosu_memory_lib::MemorySource;
+
+fn install(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "rosu-memory-lib-cache-{name}-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("osu!.exe"), b"osu!").unwrap();
+    fs::write(dir.join("osu!.someone.cfg"), "LastVersion = b20250702.1\n").unwrap();
+    dir
+}
+
+#[test]
+fn invalidated_by_another_build() {
+    let dir = install("build");
+    let mut fake = FakeProcess::new().with_executable_dir(&dir);
+    let placed = fake.place_signatures();
+    let cache = SignatureCache::new(dir.join("signatures.cache"));
+
+    assert!(cache.load(&fake).is_none());
+    cache.load_or_scan(&fake).unwrap();
+    let cached = cache.load(&fake).unwrap();
+    assert_eq!(cached.report(), placed.report());
+
+    fs::write(dir.join("osu!.someone.cfg"), "LastVersion = b20250703.1\n").unwrap();
+    assert!(cache.load(&fake).is_none());
+    cache.load_or_scan(&fake).unwrap();
+    assert!(cache.load(&fake).is_some());
+
+    // same size, only the modification time tells the executables apart
+    File::options()
+        .write(true)
+        .open(dir.join("osu!.exe"))
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+    assert!(cache.load(&fake).is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn stale_addresses_are_rejected() {
+    let dir = install("stale");
+    let mut fake = FakeProcess::new().with_executable_dir(&dir);
+    let placed = fake.place_signatures();
+    let cache = SignatureCache::new(dir.join("signatures.cache"));
+    cache.load_or_scan(&fake).unwrap();
+
+    fake.write_u8(placed.status, !fake.read_u8(placed.status).unwrap());
+    assert!(cache.load(&fake).is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+
+use rosu_memory_lib::reader::beatmap::common::{BeatmapHitWindows, BeatmapModdedStats};
+use rosu_memory_lib::reader::beatmap::difficulty::modded_stats;
+use rosu_memory_lib::reader::common::GameMode;
+use rosu_mods::GameModsLegacy;
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,1500,1,0,0:0:0:0:
+256,192,2000,2,0,L|356:192,1,100
+256,192,2500,1,0,0:0:0:0:
+256,192,3000,1,0,0:0:0:0:
+256,192,3500,1,0,0:0:0:0:
+";
+
+fn osu_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "rosu-memory-lib-difficulty-{name}-{}.osu",
+        std::process::id()
+    ));
+    std::fs::write(&path, OSU_FILE).unwrap();
+    path
+}
+
+fn stats(name: &str, mode: GameMode, mods: GameModsLegacy) -> BeatmapModdedStats {
+    let path = osu_file(name);
+    let stats = modded_stats(&path, mode, mods).unwrap();
+    std::fs::remove_file(path).unwrap();
+    stats
+}
+
+#[test]
+fn mods_change_stats_and_windows() {
+    let nomod = stats("nomod", GameMode::Osu, GameModsLegacy::NoMod);
+    assert_eq!(nomod.mode, GameMode::Osu);
+    assert_eq!(
+        (nomod.bpm, nomod.length, nomod.drain_time),
+        (120.0, 3500, 2500)
+    );
+    assert_eq!(
+        nomod.hit_windows,
+        BeatmapHitWindows {
+            preempt: 600.0,
+            great: 32.0,
+            ok: Some(76.0),
+            meh: Some(120.0),
+            ..Default::default()
+        }
+    );
+
+    let dt = stats("dt", GameMode::Osu, GameModsLegacy::DoubleTime);
+    assert_eq!(dt.clock_rate, 1.5);
+    assert_eq!((dt.bpm, dt.length), (180.0, 2333));
+    assert!((dt.hit_windows.preempt - 400.0).abs() < 1e-9);
+
+    let hr = stats("hr", GameMode::Osu, GameModsLegacy::HardRock);
+    assert_eq!((hr.ar, hr.od), (10.0, 10.0));
+    assert!((hr.cs - 5.2).abs() < 1e-6 && (hr.hp - 7.0).abs() < 1e-6);
+    assert_eq!(hr.hit_windows.great, 20.0);
+
+    let ez = stats("ez", GameMode::Osu, GameModsLegacy::Easy);
+    assert_eq!((ez.ar, ez.od, ez.cs, ez.hp), (4.5, 4.0, 2.0, 2.5));
+    assert_eq!(ez.hit_windows.great, 56.0);
+}
+
+#[test]
+fn converts_to_the_selected_mode() {
+    let mania = stats("mania", GameMode::Mania, GameModsLegacy::NoMod);
+    assert_eq!(mania.mode, GameMode::Mania);
+    assert_eq!(mania.cs, 7.0);
+    assert_eq!(
+        mania.hit_windows,
+        BeatmapHitWindows {
+            preempt: 0.0,
+            perfect: Some(16.0),
+            great: 34.0,
+            good: Some(67.0),
+            ok: Some(97.0),
+            meh: Some(121.0),
+        }
+    );
+
+    let hr = stats("mania-hr", GameMode::Mania, GameModsLegacy::HardRock);
+    assert_eq!(hr.hit_windows.great, 24.0);
+    assert_eq!(hr.hit_windows.perfect, Some(11.0));
+
+    let taiko = stats("taiko", GameMode::Taiko, GameModsLegacy::NoMod);
+    assert_eq!(taiko.mode, GameMode::Taiko);
+    assert_eq!(taiko.hit_windows.meh, None);
+}
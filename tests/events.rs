@@ -0,0 +1,218 @@
+#![cfg(feature = "testing")]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rosu_mem::signature::Signature;
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::common::stable::offset::COMMON_OFFSET;
+use rosu_memory_lib::reader::common::GameState;
+use rosu_memory_lib::reader::events::{EventPoller, GameEvent};
+use rosu_memory_lib::reader::gameplay::stable::offset::GAMEPLAY_OFFSET;
+use rosu_memory_lib::reader::structs::{State, StaticAddresses};
+use rosu_memory_lib::{Error, MemorySource};
+use rosu_mods::GameModsLegacy;
+
+/// Fake osu! shared between the test and the poller
+#[derive(Clone)]
+struct Osu {
+    fake: Arc<Mutex<FakeProcess>>,
+    status: i32,
+    replay: i32,
+    score_base: i32,
+    hp: i32,
+    /// Set once the poller owning this clone is dropped
+    dropped: Arc<AtomicBool>,
+    owned: bool,
+}
+
+impl Osu {
+    fn new() -> Self {
+        let mut fake = FakeProcess::new();
+        let a = fake.place_signatures();
+        let status = fake.pointer_chain(a.status, &[-COMMON_OFFSET.status]);
+        let replay = fake.pointer_chain(a.check_replay_addr, &[COMMON_OFFSET.check_replay]);
+        fake.pointer_chain(a.base, &[COMMON_OFFSET.pause_ptr]);
+        fake.pointer_chain(a.base, &[-GAMEPLAY_OFFSET.ruleset]);
+        fake.pointer_chain(a.menu_mods, &[COMMON_OFFSET.mods_ptr]);
+        let gameplay = [
+            -GAMEPLAY_OFFSET.ptr,
+            GAMEPLAY_OFFSET.addr,
+            GAMEPLAY_OFFSET.base,
+        ];
+        let score_base = fake.pointer_chain(
+            a.rulesets,
+            &[gameplay.as_slice(), &[GAMEPLAY_OFFSET.score_base]].concat(),
+        );
+        fake.pointer_chain(score_base, &[GAMEPLAY_OFFSET.mods]);
+        let hp = fake.pointer_chain(
+            a.rulesets,
+            &[gameplay.as_slice(), &[GAMEPLAY_OFFSET.hp_base]].concat(),
+        ) + GAMEPLAY_OFFSET.hp;
+        Self {
+            fake: Arc::new(Mutex::new(fake)),
+            status,
+            replay,
+            score_base,
+            hp,
+            dropped: Arc::default(),
+            owned: false,
+        }
+    }
+
+    fn poller(&self) -> EventPoller<Osu> {
+        let state = State {
+            addresses: StaticAddresses::new(self).unwrap(),
+        };
+        let mut owned = self.clone();
+        owned.owned = true;
+        EventPoller::new(owned, state).interval(Duration::from_millis(10))
+    }
+
+    fn with(&self, f: impl FnOnce(&mut FakeProcess)) {
+        f(&mut self.fake.lock().unwrap());
+    }
+
+    fn set_state(&self, game_state: GameState) {
+        self.with(|fake| fake.write_u32(self.status, game_state as u32));
+    }
+
+    fn set_hp(&self, hp: f64) {
+        self.with(|fake| fake.write_f64(self.hp, hp));
+    }
+
+    fn set_mods(&self, mods: GameModsLegacy) {
+        let score_base = self.score_base;
+        self.with(|fake| {
+            let xor = MemorySource::read_i32(&*fake, score_base + GAMEPLAY_OFFSET.mods).unwrap();
+            fake.write_u32(xor + GAMEPLAY_OFFSET.mods_xor2, mods.bits());
+        });
+    }
+
+    fn set_replay(&self, replay: bool) {
+        self.with(|fake| fake.write_u8(self.replay, replay as u8));
+    }
+}
+
+impl Drop for Osu {
+    fn drop(&mut self) {
+        if self.owned {
+            self.dropped.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+impl MemorySource for Osu {
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error> {
+        self.fake.lock().unwrap().read(addr, len, buff)
+    }
+
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error> {
+        self.fake.lock().unwrap().read_signature(sign)
+    }
+}
+
+/// Plays until HP drops to zero with `mods`, returns every event
+fn play_to_zero_hp(mods: GameModsLegacy) -> Vec<GameEvent> {
+    let osu = Osu::new();
+    osu.set_state(GameState::SongSelect);
+    let mut poller = osu.poller();
+    let mut events = poller.poll().unwrap();
+
+    osu.set_state(GameState::Playing);
+    osu.set_mods(mods);
+    osu.set_hp(200.0);
+    events.extend(poller.poll().unwrap());
+    osu.set_hp(0.0);
+    events.extend(poller.poll().unwrap());
+    events
+}
+
+#[test]
+fn fails_only_with_mods_that_can_fail() {
+    assert!(play_to_zero_hp(GameModsLegacy::Hidden).contains(&GameEvent::PlayFailed));
+    for mods in [
+        GameModsLegacy::NoFail,
+        GameModsLegacy::Relax,
+        GameModsLegacy::Autopilot,
+        GameModsLegacy::Autoplay,
+    ] {
+        assert!(
+            !play_to_zero_hp(mods).contains(&GameEvent::PlayFailed),
+            "{mods} failed"
+        );
+    }
+}
+
+#[test]
+fn passes_on_any_result_screen_but_not_in_replays() {
+    for (replay, result_screen, passed) in [
+        (false, GameState::ResultScreen, true),
+        (false, GameState::MultiplayerResultScreen, true),
+        (true, GameState::ResultScreen, false),
+    ] {
+        let osu = Osu::new();
+        osu.set_state(GameState::SongSelect);
+        let mut poller = osu.poller();
+        poller.poll().unwrap();
+
+        osu.set_replay(replay);
+        osu.set_state(GameState::Playing);
+        let started = poller.poll().unwrap();
+        assert_eq!(started.contains(&GameEvent::ReplayStarted), replay);
+
+        osu.set_state(result_screen);
+        let ended = poller.poll().unwrap();
+        assert_eq!(ended.contains(&GameEvent::PlayPassed), passed);
+    }
+}
+
+#[test]
+fn replay_flag_lagging_behind_still_starts_a_replay() {
+    let osu = Osu::new();
+    osu.set_state(GameState::SongSelect);
+    let mut poller = osu.poller();
+    poller.poll().unwrap();
+
+    osu.set_state(GameState::Playing);
+    let started = poller.poll().unwrap();
+    assert!(started.contains(&GameEvent::PlayStarted));
+
+    osu.set_replay(true);
+    let flagged = poller.poll().unwrap();
+    assert_eq!(
+        flagged
+            .iter()
+            .filter(|event| **event == GameEvent::ReplayStarted)
+            .count(),
+        1
+    );
+    assert!(!poller.poll().unwrap().contains(&GameEvent::ReplayStarted));
+
+    osu.set_state(GameState::ResultScreen);
+    let ended = poller.poll().unwrap();
+    assert!(!ended.contains(&GameEvent::PlayPassed));
+}
+
+#[test]
+fn channel_thread_stops_when_dropped() {
+    let osu = Osu::new();
+    osu.set_state(GameState::SongSelect);
+    let events = osu.poller().into_channel();
+    assert!(matches!(
+        events.recv_timeout(Duration::from_secs(5)),
+        Ok(Ok(GameEvent::GameStateChanged { .. }))
+    ));
+
+    // nothing happens in game anymore, the thread has nothing to send
+    drop(events);
+    let start = Instant::now();
+    while !osu.dropped.load(Ordering::SeqCst) {
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "poller kept running"
+        );
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,203 @@
+#![cfg(feature = "testing")]
+
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::beatmap::stable::offset::BEATMAP_OFFSET;
+use rosu_memory_lib::reader::beatmap::BeatmapReader;
+use rosu_memory_lib::reader::common::stable::offset::COMMON_OFFSET;
+use rosu_memory_lib::reader::common::{CommonReader, GameMode, GameState, OsuClientKind};
+use rosu_memory_lib::reader::gameplay::stable::offset::GAMEPLAY_OFFSET;
+use rosu_memory_lib::reader::gameplay::GameplayReader;
+use rosu_memory_lib::reader::resultscreen::stable::offset::RESULT_SCREEN_OFFSET;
+use rosu_memory_lib::reader::resultscreen::ResultScreenReader;
+use rosu_memory_lib::reader::structs::{State, StaticAddresses};
+use rosu_memory_lib::reader::user::stable::offset::USER_PROFILE_OFFSET;
+use rosu_memory_lib::reader::user::UserReader;
+use rosu_memory_lib::Error;
+use rosu_mods::GameModsLegacy;
+
+/// Fake osu! with every signature placed, the game state set and the menu
+/// pointers allocated
+fn osu(game_state: GameState) -> (FakeProcess, StaticAddresses) {
+    let mut fake = FakeProcess::new();
+    let addresses = fake.place_signatures();
+    let status = fake.pointer_chain(addresses.status, &[-COMMON_OFFSET.status]);
+    fake.write_u32(status, game_state as u32);
+    fake.pointer_chain(addresses.menu_mods, &[COMMON_OFFSET.mods_ptr]);
+    fake.pointer_chain(addresses.base, &[COMMON_OFFSET.ruleset_ptr]);
+    fake.pointer_chain(addresses.playtime, &[COMMON_OFFSET.ig_time]);
+    (fake, addresses)
+}
+
+fn attach(fake: &FakeProcess) -> State {
+    State {
+        addresses: StaticAddresses::new(fake).unwrap(),
+    }
+}
+
+#[test]
+fn resolves_placed_signatures() {
+    let (fake, addresses) = osu(GameState::MainMenu);
+    let state = attach(&fake);
+    assert_eq!(state.addresses.status, addresses.status);
+    assert_eq!(state.addresses.rulesets, addresses.rulesets);
+}
+
+#[test]
+fn common() {
+    let (mut fake, addresses) = osu(GameState::SongSelect);
+    let mods = fake.pointer_chain(addresses.menu_mods, &[COMMON_OFFSET.mods_ptr]);
+    fake.write_u32(
+        mods,
+        (GameModsLegacy::Hidden | GameModsLegacy::HardRock).bits(),
+    );
+    let ruleset = fake.pointer_chain(addresses.base, &[COMMON_OFFSET.ruleset_ptr]);
+    fake.write_i32(ruleset + COMMON_OFFSET.ruleset, 3);
+
+    let mut state = attach(&fake);
+    let mut reader = CommonReader::new(&fake, &mut state, OsuClientKind::Stable);
+    assert_eq!(reader.game_state().unwrap(), GameState::SongSelect);
+    assert_eq!(
+        reader.menu_mods().unwrap(),
+        GameModsLegacy::Hidden | GameModsLegacy::HardRock
+    );
+    assert_eq!(reader.menu_ruleset().unwrap(), GameMode::Mania);
+}
+
+#[test]
+fn beatmap() {
+    let (mut fake, addresses) = osu(GameState::SongSelect);
+    let beatmap = fake.pointer_chain(addresses.base, &[-BEATMAP_OFFSET.ptr, 0]);
+    fake.write_string(beatmap + BEATMAP_OFFSET.technical.md5, "d41d8cd9");
+    fake.write_string(
+        beatmap + BEATMAP_OFFSET.metadata.title_romanized,
+        "Blue Zenith",
+    );
+    fake.write_string(
+        beatmap + BEATMAP_OFFSET.metadata.difficulty,
+        "FOUR DIMENSIONS",
+    );
+    fake.write_i32(beatmap + BEATMAP_OFFSET.technical.id, 658127);
+    fake.write_f32(beatmap + BEATMAP_OFFSET.stats.ar, 9.8);
+    fake.write_f32(beatmap + BEATMAP_OFFSET.stats.od, 9.0);
+    fake.write_i32(beatmap + BEATMAP_OFFSET.technical.mode, 1);
+
+    let mut state = attach(&fake);
+    let mut reader = BeatmapReader::new(&fake, &mut state, OsuClientKind::Stable).unwrap();
+    assert_eq!(reader.md5().unwrap(), "d41d8cd9");
+    assert_eq!(reader.title_romanized().unwrap(), "Blue Zenith");
+    assert_eq!(reader.difficulty().unwrap(), "FOUR DIMENSIONS");
+    assert_eq!(reader.id().unwrap(), 658127);
+    assert_eq!(reader.ar().unwrap(), 9.8);
+    assert_eq!(reader.od().unwrap(), 9.0);
+    assert_eq!(reader.mode().unwrap(), GameMode::Taiko);
+}
+
+#[test]
+fn gameplay() {
+    let (mut fake, addresses) = osu(GameState::Playing);
+    let score_base = fake.pointer_chain(
+        addresses.rulesets,
+        &[
+            -GAMEPLAY_OFFSET.ptr,
+            GAMEPLAY_OFFSET.addr,
+            GAMEPLAY_OFFSET.base,
+            GAMEPLAY_OFFSET.score_base,
+        ],
+    );
+    fake.write_i32(score_base + GAMEPLAY_OFFSET.score, 727_000);
+    fake.write_i16(score_base + GAMEPLAY_OFFSET.combo, 42);
+    fake.write_i16(score_base + GAMEPLAY_OFFSET.max_combo, 100);
+    fake.write_i16(score_base + GAMEPLAY_OFFSET.hits._300, 95);
+    fake.write_i16(score_base + GAMEPLAY_OFFSET.hits._100, 4);
+    fake.write_i16(score_base + GAMEPLAY_OFFSET.hits._miss, 1);
+    fake.write_string(score_base + GAMEPLAY_OFFSET.username, "peppy");
+    let mods = fake.pointer_chain(score_base, &[GAMEPLAY_OFFSET.mods]);
+    // osu! keeps the mods xored with a random key
+    fake.write_u32(mods + GAMEPLAY_OFFSET.mods_xor, 0xFF);
+    fake.write_u32(
+        mods + GAMEPLAY_OFFSET.mods_xor2,
+        0xFF ^ GameModsLegacy::DoubleTime.bits(),
+    );
+    fake.pointer_chain(
+        addresses.rulesets,
+        &[
+            -GAMEPLAY_OFFSET.ptr,
+            GAMEPLAY_OFFSET.addr,
+            GAMEPLAY_OFFSET.base,
+            GAMEPLAY_OFFSET.hp_base,
+        ],
+    );
+    fake.pointer_chain(addresses.base, &[-GAMEPLAY_OFFSET.ruleset]);
+
+    let mut state = attach(&fake);
+    let mut reader = GameplayReader::new(&fake, &mut state, OsuClientKind::Stable);
+    assert_eq!(reader.score().unwrap(), 727_000);
+    assert_eq!(reader.combo().unwrap(), 42);
+    assert_eq!(reader.mods().unwrap(), GameModsLegacy::DoubleTime);
+
+    let info = reader.info().unwrap();
+    assert_eq!(info.username, "peppy");
+    assert_eq!(info.max_combo, 100);
+    assert_eq!(info.hits._300, 95);
+    assert_eq!(info.hits._100, 4);
+    assert_eq!(info.hits._miss, 1);
+}
+
+#[test]
+fn result_screen() {
+    let (mut fake, addresses) = osu(GameState::ResultScreen);
+    let base = fake.pointer_chain(
+        addresses.rulesets,
+        &[
+            -RESULT_SCREEN_OFFSET.ptr,
+            RESULT_SCREEN_OFFSET.addr,
+            RESULT_SCREEN_OFFSET.base,
+        ],
+    );
+    fake.write_string(base + RESULT_SCREEN_OFFSET.username, "peppy");
+    fake.write_i32(base + RESULT_SCREEN_OFFSET.score, 1_000_000);
+    fake.write_i16(base + RESULT_SCREEN_OFFSET.max_combo, 300);
+    fake.write_i16(base + RESULT_SCREEN_OFFSET.hits._300, 300);
+
+    let mut state = attach(&fake);
+    let mut reader = ResultScreenReader::new(&fake, &mut state, OsuClientKind::Stable);
+    assert_eq!(reader.username().unwrap(), "peppy");
+    assert_eq!(reader.score().unwrap(), 1_000_000);
+    assert_eq!(reader.max_combo().unwrap(), 300);
+    assert_eq!(reader.hits().unwrap()._300, 300);
+    assert_eq!(reader.accuracy().unwrap(), 100.0);
+}
+
+#[test]
+fn user() {
+    let (mut fake, addresses) = osu(GameState::MainMenu);
+    let user = fake.pointer_chain(addresses.user_profile, &[USER_PROFILE_OFFSET.ptr, 0]);
+    fake.write_string(user + USER_PROFILE_OFFSET.username, "peppy");
+    fake.write_i32(user + USER_PROFILE_OFFSET.id, 2);
+    fake.write_i32(user + USER_PROFILE_OFFSET.rank, 1);
+
+    let mut state = attach(&fake);
+    let info = UserReader::new(&fake, &mut state, OsuClientKind::Stable)
+        .info()
+        .unwrap();
+    assert_eq!(info.username, "peppy");
+    assert_eq!(info.id, 2);
+    assert_eq!(info.rank, 1);
+}
+
+#[test]
+fn wrong_game_state() {
+    let (fake, _) = osu(GameState::SongSelect);
+    let mut state = attach(&fake);
+    let mut reader = GameplayReader::new(&fake, &mut state, OsuClientKind::Stable);
+    assert!(matches!(reader.score(), Err(Error::NotAvailable(_))));
+}
+
+#[test]
+fn unmapped_memory() {
+    let fake = FakeProcess::new();
+    assert!(matches!(
+        rosu_memory_lib::MemorySource::read_i32(&fake, 0x1234),
+        Err(Error::MemoryRead(_))
+    ));
+}
@@ -0,0 +1,35 @@
+use rosu_memory_lib::reader::common::GameMode;
+use rosu_memory_lib::reader::grade::{calculate_accuracy, calculate_accuracy_v2, grade, Grade};
+use rosu_memory_lib::reader::structs::Hit;
+use rosu_mods::GameModsLegacy;
+
+#[test]
+fn counts_above_i16_once_summed() {
+    let mania = Hit {
+        _geki: 20_000,
+        _300: 20_000,
+        ..Default::default()
+    };
+    assert_eq!(calculate_accuracy(&GameMode::Mania, &mania).unwrap(), 100.0);
+    let v2 = calculate_accuracy_v2(&GameMode::Mania, &mania).unwrap();
+    assert!((v2 - 99.180_327).abs() < 1e-4, "{v2}");
+    assert_eq!(
+        grade(&GameMode::Mania, &mania, GameModsLegacy::ScoreV2),
+        Grade::S
+    );
+
+    let osu = Hit {
+        _300: 30_000,
+        _100: 10_000,
+        ..Default::default()
+    };
+    let accuracy = calculate_accuracy(&GameMode::Osu, &osu).unwrap();
+    assert!((accuracy - 83.333_333).abs() < 1e-4, "{accuracy}");
+
+    let catch = Hit {
+        _300: 30_000,
+        _katu: 10_000,
+        ..Default::default()
+    };
+    assert_eq!(calculate_accuracy(&GameMode::Catch, &catch).unwrap(), 75.0);
+}
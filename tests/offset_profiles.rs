@@ -0,0 +1,51 @@
+use rosu_memory_lib::reader::common::OsuVersion;
+use rosu_memory_lib::reader::offsets::{
+    OffsetProfile, OffsetProfiles, OffsetTable, LATEST_KNOWN_BUILD,
+};
+
+fn version(value: &str) -> OsuVersion {
+    value.parse().unwrap()
+}
+
+fn profile(name: &str, min: Option<&str>, max: Option<&str>) -> OffsetProfile {
+    OffsetProfile {
+        name: name.to_string(),
+        min: min.map(version),
+        max: max.map(version),
+        offsets: OffsetTable::default(),
+    }
+}
+
+#[test]
+fn builtin_covers_builds_after_the_latest_known_one() {
+    let profiles = OffsetProfiles::builtin();
+    for build in [
+        LATEST_KNOWN_BUILD,
+        version("b20251231.1"),
+        version("b20990101"),
+    ] {
+        assert_eq!(profiles.select(&build).unwrap().name, "builtin");
+    }
+}
+
+#[test]
+fn newest_matching_profile_wins() {
+    let mut profiles = OffsetProfiles::builtin();
+    profiles
+        .push(profile("2026", Some("b20260101"), None))
+        .unwrap();
+    profiles
+        .push(profile("hotfix", Some("b20260301"), Some("b20260301.2")))
+        .unwrap();
+
+    assert_eq!(
+        profiles.select(&version("b20251231")).unwrap().name,
+        "builtin"
+    );
+    assert_eq!(profiles.select(&version("b20260201")).unwrap().name, "2026");
+    assert_eq!(
+        profiles.select(&version("b20260301.1")).unwrap().name,
+        "hotfix"
+    );
+    assert_eq!(profiles.select(&version("b20260302")).unwrap().name, "2026");
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "testing")]
+
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::beatmap::stable::offset::BEATMAP_OFFSET;
+use rosu_memory_lib::reader::common::stable::offset::COMMON_OFFSET;
+use rosu_memory_lib::reader::common::{GameMode, GameState};
+use rosu_memory_lib::reader::offsets::{set_offsets, OffsetTable};
+use rosu_memory_lib::reader::overlay::stable::memory::key_overlay_std;
+use rosu_memory_lib::reader::structs::{State, StaticAddresses};
+use rosu_memory_lib::reader::user::stable::offset::USER_PROFILE_OFFSET;
+
+#[test]
+fn keys_follow_the_offset_table() {
+    let mut table = OffsetTable::default();
+    table.overlay.base = 0xc0;
+    table.overlay.pressed = 0x20;
+    table.overlay.count = 0x24;
+    table.overlay.keys.key_1 = 0x18;
+    table.overlay.keys.key_2 = 0x1c;
+    table.overlay.keys.mouse_1 = 0x20;
+    table.overlay.keys.mouse_2 = 0x24;
+    set_offsets(table.clone()).unwrap();
+
+    let mut fake = FakeProcess::new();
+    let addresses = fake.place_signatures();
+    let status = fake.pointer_chain(addresses.status, &[-COMMON_OFFSET.status]);
+    fake.write_u32(status, GameState::Playing as u32);
+    let user = fake.pointer_chain(addresses.user_profile, &[USER_PROFILE_OFFSET.ptr, 0]);
+    fake.write_i32(user + USER_PROFILE_OFFSET.playmode, 0);
+    let beatmap = fake.pointer_chain(addresses.base, &[-BEATMAP_OFFSET.ptr, 0]);
+    fake.write_i32(
+        beatmap + BEATMAP_OFFSET.technical.mode,
+        GameMode::Osu as i32,
+    );
+
+    let overlay = &table.overlay;
+    let keys = fake.pointer_chain(
+        addresses.rulesets,
+        &[
+            -overlay.ptr,
+            overlay.addr,
+            overlay.base,
+            overlay.container,
+            overlay.array,
+        ],
+    );
+    fake.write_i32(keys + overlay.size, 4);
+    for (i, slot) in [
+        overlay.keys.key_1,
+        overlay.keys.key_2,
+        overlay.keys.mouse_1,
+        overlay.keys.mouse_2,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let key = fake.pointer_chain(keys, &[slot]);
+        fake.write_i32(key + overlay.pressed, (i % 2) as i32);
+        fake.write_i32(key + overlay.count, 10 + i as i32);
+    }
+
+    let mut state = State {
+        addresses: StaticAddresses::new(&fake).unwrap(),
+    };
+    let keys = key_overlay_std(&fake, &mut state);
+    set_offsets(OffsetTable::default()).unwrap();
+
+    let keys = keys.unwrap();
+    assert_eq!(
+        [keys.key_1, keys.key_2, keys.mouse_1, keys.mouse_2].map(|key| (key.pressed, key.count)),
+        [(false, 10), (true, 11), (false, 12), (true, 13)]
+    );
+}
+
+#[test]
+fn out_of_range_overlay_offsets_are_rejected() {
+    let mut table = OffsetTable::default();
+    table.overlay.keys.mouse_2 = 0x20000;
+    let error = table.validate().unwrap_err().to_string();
+    assert!(error.contains("overlay.keys.mouse_2"), "{error}");
+}
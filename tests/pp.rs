@@ -0,0 +1,147 @@
+use rosu_memory_lib::reader::common::GameMode;
+use rosu_memory_lib::reader::gameplay::common::GameplayInfo;
+use rosu_memory_lib::reader::grade::Grade;
+use rosu_memory_lib::reader::pp::LivePp;
+use rosu_memory_lib::reader::structs::Hit;
+use rosu_mods::GameModsLegacy;
+use rosu_pp::model::mode::GameMode as PpMode;
+use rosu_pp::{Beatmap, Difficulty, Performance};
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+64,192,1000,1,0,0:0:0:0:
+128,192,1250,1,0,0:0:0:0:
+192,192,1500,1,0,0:0:0:0:
+256,192,1750,1,0,0:0:0:0:
+320,192,2000,2,0,L|448:192,1,140
+64,192,3000,1,0,0:0:0:0:
+128,192,3250,1,0,0:0:0:0:
+192,192,3500,1,0,0:0:0:0:
+";
+
+fn beatmap() -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("rosu-memory-lib-pp-{}.osu", std::process::id()));
+    std::fs::write(&path, OSU_FILE).unwrap();
+    path
+}
+
+fn gameplay(mode: GameMode, hits: Hit) -> GameplayInfo {
+    GameplayInfo {
+        score: 0,
+        mods: GameModsLegacy::NoMod,
+        combo: 1,
+        max_combo: 1,
+        hp: 200.0,
+        username: String::new(),
+        ig_time: 1000,
+        retries: 0,
+        hits,
+        mode,
+        accuracy: 0.0,
+        accuracy_v2: 0.0,
+        grade: Grade::D,
+    }
+}
+
+/// pp of an SS on the beatmap converted to `mode`
+fn ss(path: &std::path::Path, mode: PpMode) -> f64 {
+    let map = Beatmap::from_path(path)
+        .unwrap()
+        .convert(mode, &GameModsLegacy::NoMod.into())
+        .unwrap();
+    let attributes = Difficulty::new().lazer(false).calculate(&map);
+    Performance::new(attributes).lazer(false).calculate().pp()
+}
+
+#[test]
+fn converts_to_the_mode_played() {
+    let path = beatmap();
+    let mut live = LivePp::new();
+    live.set_beatmap(&path).unwrap();
+
+    for (mode, pp_mode) in [
+        (GameMode::Osu, PpMode::Osu),
+        (GameMode::Taiko, PpMode::Taiko),
+        (GameMode::Catch, PpMode::Catch),
+        (GameMode::Mania, PpMode::Mania),
+    ] {
+        let info = live.update(&gameplay(mode, Hit::default())).unwrap();
+        let expected = ss(&path, pp_mode);
+        assert!(
+            (info.max - expected).abs() < 1e-9,
+            "{mode:?}: {} != {expected}",
+            info.max
+        );
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn if_fc_counts_misses_and_unjudged_objects_as_perfect() {
+    let path = beatmap();
+    let mut live = LivePp::new();
+    live.set_beatmap(&path).unwrap();
+
+    for (mode, pp_mode, hits) in [
+        (
+            GameMode::Osu,
+            PpMode::Osu,
+            Hit {
+                _300: 2,
+                _miss: 1,
+                ..Default::default()
+            },
+        ),
+        (
+            GameMode::Taiko,
+            PpMode::Taiko,
+            Hit {
+                _300: 2,
+                _miss: 1,
+                ..Default::default()
+            },
+        ),
+        (
+            GameMode::Catch,
+            PpMode::Catch,
+            Hit {
+                _300: 2,
+                _miss: 1,
+                ..Default::default()
+            },
+        ),
+        (
+            GameMode::Mania,
+            PpMode::Mania,
+            Hit {
+                _geki: 2,
+                _miss: 1,
+                ..Default::default()
+            },
+        ),
+    ] {
+        let info = live.update(&gameplay(mode, hits)).unwrap();
+        let expected = ss(&path, pp_mode);
+        assert!(
+            (info.if_fc - expected).abs() < 1e-9,
+            "{mode:?}: {} != {expected}",
+            info.if_fc
+        );
+    }
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,188 @@
+#![cfg(feature = "testing")]
+
+use std::path::PathBuf;
+
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::beatmap::stable::offset::BEATMAP_OFFSET;
+use rosu_memory_lib::reader::common::stable::offset::COMMON_OFFSET;
+use rosu_memory_lib::reader::common::{GameMode, GameState, OsuClientKind};
+use rosu_memory_lib::reader::grade::Grade;
+use rosu_memory_lib::reader::resultscreen::stable::offset::RESULT_SCREEN_OFFSET;
+use rosu_memory_lib::reader::resultscreen::ResultScreenReader;
+use rosu_memory_lib::reader::structs::{State, StaticAddresses};
+use rosu_pp::{Beatmap, Difficulty, Performance};
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+100,100,1250,1,0,0:0:0:0:
+400,300,1500,1,0,0:0:0:0:
+256,192,2000,2,0,L|356:192,1,100
+50,350,2500,1,0,0:0:0:0:
+450,50,3000,1,0,0:0:0:0:
+";
+
+/// 2025-07-02 12:00:00 as .NET ticks of a local `DateTime`
+const DATE_TICKS: i64 = (0x2 << 62) | 638_870_544_000_000_000;
+const DATE_MILLIS: i64 = 1_751_457_600_000;
+
+/// Result screen of a score on [`OSU_FILE`]
+struct World {
+    dir: PathBuf,
+    fake: FakeProcess,
+    addresses: StaticAddresses,
+}
+
+impl World {
+    fn new(name: &str, n300: i16, max_combo: i16) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "rosu-memory-lib-resultscreen-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("Songs").join("1 Test")).unwrap();
+        std::fs::write(dir.join("Songs").join("1 Test").join("test.osu"), OSU_FILE).unwrap();
+
+        let mut fake = FakeProcess::new().with_executable_dir(&dir);
+        let addresses = fake.place_signatures();
+        let status = fake.pointer_chain(addresses.status, &[-COMMON_OFFSET.status]);
+        fake.write_u32(status, GameState::ResultScreen as u32);
+        let settings = fake.pointer_chain(
+            addresses.settings,
+            &[COMMON_OFFSET.settings_ptr, COMMON_OFFSET.settings_addr],
+        );
+        fake.write_string(settings + COMMON_OFFSET.path, "Songs");
+
+        let beatmap = fake.pointer_chain(addresses.base, &[-BEATMAP_OFFSET.ptr, 0]);
+        fake.write_string(beatmap + BEATMAP_OFFSET.technical.md5, name);
+        fake.write_string(beatmap + BEATMAP_OFFSET.location.folder, "1 Test");
+        fake.write_string(beatmap + BEATMAP_OFFSET.location.filename, "test.osu");
+
+        let offset = &RESULT_SCREEN_OFFSET;
+        let base = fake.pointer_chain(addresses.rulesets, &[-offset.ptr, offset.addr, offset.base]);
+        fake.write_string(base + offset.username, "someone");
+        fake.write_i32(base + offset.mode, GameMode::Osu as i32);
+        fake.pointer_chain(base, &[offset.mods]);
+        fake.write_i16(base + offset.hits._300, n300);
+        fake.write_i16(base + offset.max_combo, max_combo);
+        fake.write_i64(base + offset.date, DATE_TICKS);
+
+        Self {
+            dir,
+            fake,
+            addresses,
+        }
+    }
+
+    fn state(&self) -> State {
+        State {
+            addresses: self.addresses.clone(),
+        }
+    }
+}
+
+impl Drop for World {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn map() -> Beatmap {
+    Beatmap::from_bytes(OSU_FILE.as_bytes()).unwrap()
+}
+
+fn full_combo() -> i16 {
+    Difficulty::new().lazer(false).calculate(&map()).max_combo() as i16
+}
+
+#[test]
+fn date_is_unix_millis() {
+    let world = World::new("date", 6, full_combo());
+    let mut state = world.state();
+    let mut reader = ResultScreenReader::new(&world.fake, &mut state, OsuClientKind::Stable);
+    assert_eq!(reader.date().unwrap(), DATE_MILLIS);
+    assert_eq!(reader.info().unwrap().date, DATE_MILLIS);
+}
+
+#[test]
+fn pass_has_grade_and_pp() {
+    let combo = full_combo();
+    let world = World::new("pass", 6, combo);
+    let mut state = world.state();
+    let mut reader = ResultScreenReader::new(&world.fake, &mut state, OsuClientKind::Stable);
+
+    let expected = Performance::new(map())
+        .lazer(false)
+        .n300(6)
+        .n100(0)
+        .n50(0)
+        .misses(0)
+        .combo(combo as u32)
+        .calculate()
+        .pp();
+    let info = reader.info().unwrap();
+    assert!(info.passed && reader.passed().unwrap());
+    assert_eq!(info.grade, Grade::SS);
+    assert_eq!(reader.grade().unwrap(), Grade::SS);
+    assert_eq!(info.pp, Some(expected));
+    assert_eq!(reader.pp().unwrap(), expected);
+    assert_eq!(info.beatmap_md5, "pass");
+}
+
+#[test]
+fn fail_is_graded_f() {
+    let world = World::new("fail", 3, 3);
+    let mut state = world.state();
+    let mut reader = ResultScreenReader::new(&world.fake, &mut state, OsuClientKind::Stable);
+
+    let expected = Performance::new(map())
+        .lazer(false)
+        .passed_objects(3)
+        .n300(3)
+        .n100(0)
+        .n50(0)
+        .misses(0)
+        .combo(3)
+        .calculate()
+        .pp();
+    let info = reader.info().unwrap();
+    assert!(!info.passed && !reader.passed().unwrap());
+    assert_eq!(info.grade, Grade::F);
+    assert_eq!(reader.grade().unwrap(), Grade::F);
+    assert_eq!(info.pp, Some(expected));
+}
+
+#[test]
+fn missing_beatmap_assumes_a_pass() {
+    let mut world = World::new("no-beatmap", 6, full_combo());
+    // no selected beatmap at all
+    world
+        .fake
+        .write_i32(world.addresses.base - BEATMAP_OFFSET.ptr, 0);
+    let mut state = world.state();
+    let mut reader = ResultScreenReader::new(&world.fake, &mut state, OsuClientKind::Stable);
+
+    let info = reader.info().unwrap();
+    assert_eq!(info.beatmap_md5, "");
+    assert!(info.passed && reader.passed().unwrap());
+    assert_eq!(info.pp, None);
+    assert!(reader.pp().is_err());
+    assert_eq!(info.grade, Grade::SS);
+    assert_eq!(reader.grade().unwrap(), Grade::SS);
+    assert_eq!(info.username, "someone");
+}
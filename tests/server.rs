@@ -0,0 +1,389 @@
+#![cfg(all(feature = "testing", feature = "server"))]
+
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rosu_mem::signature::Signature;
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::beatmap::stable::offset::BEATMAP_OFFSET;
+use rosu_memory_lib::reader::common::stable::offset::COMMON_OFFSET;
+use rosu_memory_lib::reader::common::GameState;
+use rosu_memory_lib::reader::snapshot::OsuSnapshot;
+use rosu_memory_lib::reader::structs::{State, StaticAddresses};
+use rosu_memory_lib::reader::supervisor::Supervisor;
+use rosu_memory_lib::server::{Endpoint, Poller, Server};
+use rosu_memory_lib::{Error, MemorySource};
+use rosu_mods::GameModsLegacy;
+use serde_json::Value;
+use tungstenite::WebSocket;
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+100,100,1250,1,0,0:0:0:0:
+400,300,1500,1,0,0:0:0:0:
+256,192,2000,2,0,L|356:192,1,100
+50,350,2500,1,0,0:0:0:0:
+450,50,3000,1,0,0:0:0:0:
+";
+
+/// Fake osu! shared with the test, which keeps writing to it while the
+/// server reads
+#[derive(Clone)]
+struct Osu {
+    fake: Arc<Mutex<FakeProcess>>,
+}
+
+impl MemorySource for Osu {
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error> {
+        self.fake.lock().unwrap().read(addr, len, buff)
+    }
+
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error> {
+        self.fake.lock().unwrap().read_signature(sign)
+    }
+
+    fn executable_dir(&self) -> Option<PathBuf> {
+        self.fake.lock().unwrap().executable_dir()
+    }
+}
+
+/// Song select with [`OSU_FILE`] selected in `dir/Songs/1 Test`, `mods` is
+/// where the menu mods are read from
+struct World {
+    dir: PathBuf,
+    osu: Osu,
+    addresses: StaticAddresses,
+    mods: i32,
+}
+
+impl World {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "rosu-memory-lib-server-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("Songs").join("1 Test")).unwrap();
+        std::fs::write(dir.join("Songs").join("1 Test").join("test.osu"), OSU_FILE).unwrap();
+
+        let mut fake = FakeProcess::new().with_executable_dir(&dir);
+        let addresses = fake.place_signatures();
+        let status = fake.pointer_chain(addresses.status, &[-COMMON_OFFSET.status]);
+        fake.write_u32(status, GameState::SongSelect as u32);
+        let mods = fake.pointer_chain(addresses.menu_mods, &[COMMON_OFFSET.mods_ptr]);
+        let ruleset = fake.pointer_chain(addresses.base, &[COMMON_OFFSET.ruleset_ptr]);
+        fake.write_i32(ruleset, 0);
+        let settings = fake.pointer_chain(
+            addresses.settings,
+            &[COMMON_OFFSET.settings_ptr, COMMON_OFFSET.settings_addr],
+        );
+        fake.write_string(settings + COMMON_OFFSET.path, "Songs");
+
+        let beatmap = fake.pointer_chain(addresses.base, &[-BEATMAP_OFFSET.ptr, 0]);
+        let metadata = &BEATMAP_OFFSET.metadata;
+        for offset in [
+            metadata.author,
+            metadata.creator,
+            metadata.title_romanized,
+            metadata.title_original,
+            metadata.difficulty,
+            metadata.tags,
+            BEATMAP_OFFSET.location.audio,
+            BEATMAP_OFFSET.location.cover,
+        ] {
+            fake.write_string(beatmap + offset, "");
+        }
+        // the difficulty cache is shared by the whole test binary
+        fake.write_string(
+            beatmap + BEATMAP_OFFSET.technical.md5,
+            &format!("server-{name}"),
+        );
+        fake.write_string(beatmap + BEATMAP_OFFSET.location.folder, "1 Test");
+        fake.write_string(beatmap + BEATMAP_OFFSET.location.filename, "test.osu");
+
+        Self {
+            dir,
+            osu: Osu {
+                fake: Arc::new(Mutex::new(fake)),
+            },
+            addresses,
+            mods,
+        }
+    }
+
+    fn state(&self) -> State {
+        State {
+            addresses: self.addresses.clone(),
+        }
+    }
+
+    fn osu_file(&self) -> PathBuf {
+        self.dir.join("Songs").join("1 Test").join("test.osu")
+    }
+
+    fn set_mods(&self, mods: GameModsLegacy) {
+        self.osu
+            .fake
+            .lock()
+            .unwrap()
+            .write_u32(self.mods, mods.bits());
+    }
+
+    fn poller(&self) -> Poller<Osu> {
+        poller(self.state(), self.osu.clone())
+    }
+
+    /// Serves this world with `dir/static` as the static folder
+    fn serve(&self) -> SocketAddr {
+        let address = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let server = Server::new()
+            .address(address)
+            .interval(Duration::from_millis(20))
+            .static_dir(self.dir.join("static"));
+        // the poller isn't Send, it's created by the thread running it
+        let (state, osu) = (self.state(), self.osu.clone());
+        std::thread::spawn(move || server.run_with(poller(state, osu)));
+
+        let started = Instant::now();
+        while TcpStream::connect(address).is_err() {
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "server not listening"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        address
+    }
+}
+
+fn poller(state: State, osu: Osu) -> Poller<Osu> {
+    let supervisor = Supervisor::with_attach(move || Ok((state.clone(), osu.clone())))
+        .check_interval(Duration::ZERO);
+    Poller::new(supervisor)
+}
+
+impl Drop for World {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Status code and body of `GET path`
+fn get(address: SocketAddr, path: &str) -> (u16, Vec<u8>) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: {address}\r\n\r\n").unwrap();
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).unwrap();
+
+    let end = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .expect("response head");
+    let head = String::from_utf8_lossy(&response[..end]);
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, response[end + 4..].to_vec())
+}
+
+fn get_json(address: SocketAddr, path: &str) -> Value {
+    let (status, body) = get(address, path);
+    assert_eq!(status, 200, "{path}");
+    serde_json::from_slice(&body).unwrap()
+}
+
+type Client = WebSocket<TcpStream>;
+
+fn connect(address: SocketAddr, path: &str) -> Client {
+    let stream = TcpStream::connect(address).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let (client, _) = tungstenite::client(format!("ws://{address}{path}"), stream).unwrap();
+    client
+}
+
+fn next(client: &mut Client) -> Option<Value> {
+    match client.read() {
+        Ok(message) => Some(serde_json::from_str(message.to_text().unwrap()).unwrap()),
+        Err(tungstenite::Error::Io(e))
+            if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+        {
+            None
+        }
+        Err(e) => panic!("{e}"),
+    }
+}
+
+fn set_timeout(client: &mut Client, timeout: Duration) {
+    client.get_mut().set_read_timeout(Some(timeout)).unwrap();
+}
+
+#[test]
+fn pp_beatmap_is_loaded_once_the_file_shows_up() {
+    let world = World::new("retry");
+    // the star rating gets cached while the file is there, the snapshot
+    // keeps the beatmap once it is gone
+    OsuSnapshot::read(&world.osu, &mut world.state()).unwrap();
+    std::fs::remove_file(world.osu_file()).unwrap();
+
+    let mut poller = world.poller();
+    let tick = poller.poll();
+    assert!(tick.snapshot.beatmap.is_some());
+    assert_eq!(tick.pp.stars, 0.0);
+
+    std::fs::write(world.osu_file(), OSU_FILE).unwrap();
+    let tick = poller.poll();
+    assert!(tick.pp.stars > 0.0);
+    assert!(tick.pp.accuracy[0] > 0.0);
+}
+
+#[test]
+fn endpoints_are_found_by_path() {
+    for endpoint in Endpoint::ALL {
+        assert_eq!(
+            Endpoint::from_websocket_path(endpoint.websocket_path()),
+            Some(endpoint)
+        );
+        assert_eq!(
+            Endpoint::from_json_path(endpoint.json_path()),
+            Some(endpoint)
+        );
+    }
+    assert_eq!(
+        Endpoint::from_websocket_path("/ws"),
+        Some(Endpoint::Gosumemory)
+    );
+    assert_eq!(
+        Endpoint::from_websocket_path("/websocket/v2"),
+        Some(Endpoint::TosuV2)
+    );
+    assert_eq!(Endpoint::from_json_path("/json/v2"), Some(Endpoint::TosuV2));
+    assert_eq!(Endpoint::from_websocket_path("/json"), None);
+    assert_eq!(Endpoint::from_json_path("/ws"), None);
+}
+
+#[test]
+fn serves_json_and_files() {
+    let world = World::new("http");
+    std::fs::create_dir_all(world.dir.join("static").join("overlay")).unwrap();
+    std::fs::write(
+        world.dir.join("static").join("overlay").join("index.html"),
+        "index",
+    )
+    .unwrap();
+    std::fs::write(world.dir.join("static").join("a b.txt"), "space").unwrap();
+    std::fs::write(world.dir.join("static").join("100%.txt"), "percent").unwrap();
+    std::fs::write(world.dir.join("Songs").join("1 Test").join("bg.jpg"), "bg").unwrap();
+    std::fs::write(world.dir.join("secret.txt"), "secret").unwrap();
+    let address = world.serve();
+
+    let started = Instant::now();
+    let gosumemory = loop {
+        let json = get_json(address, "/json");
+        if json["menu"]["bm"]["md5"] == "server-http" {
+            break json;
+        }
+        assert!(started.elapsed() < Duration::from_secs(5), "{json}");
+        std::thread::sleep(Duration::from_millis(20));
+    };
+    for key in ["settings", "menu", "gameplay", "resultsScreen"] {
+        assert!(gosumemory.get(key).is_some(), "{key}");
+    }
+    let tosu = get_json(address, "/json/v2");
+    for key in ["state", "beatmap", "play", "performance", "folders"] {
+        assert!(tosu.get(key).is_some(), "{key}");
+    }
+
+    assert_eq!(get(address, "/overlay/"), (200, b"index".to_vec()));
+    // paths are percent decoded, a `%` not followed by two hex digits is kept
+    assert_eq!(get(address, "/a%20b.txt"), (200, b"space".to_vec()));
+    assert_eq!(get(address, "/100%.txt"), (200, b"percent".to_vec()));
+    assert_eq!(
+        get(address, "/Songs/1%20Test/bg.jpg"),
+        (200, b"bg".to_vec())
+    );
+    assert_eq!(get(address, "/missing.txt").0, 404);
+
+    // neither folder can be escaped, encoded or not
+    for path in [
+        "/../secret.txt",
+        "/..%2Fsecret.txt",
+        "/%2E%2E/secret.txt",
+        "/Songs/../secret.txt",
+        "/Songs/..%2Fsecret.txt",
+        "/Songs/1%20Test/..%2F..%2Fsecret.txt",
+    ] {
+        assert_eq!(get(address, path).0, 404, "{path}");
+    }
+}
+
+#[test]
+fn broadcasts_full_messages_and_patches() {
+    let world = World::new("websocket");
+    let address = world.serve();
+    let started = Instant::now();
+    while get_json(address, "/json")["menu"]["bm"]["md5"] != "server-websocket" {
+        assert!(started.elapsed() < Duration::from_secs(5));
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    let mut full = connect(address, "/ws");
+    let mut patches = connect(address, "/ws?diff=true");
+    for client in [&mut full, &mut patches] {
+        let first = next(client).unwrap();
+        assert_eq!(first["menu"]["bm"]["md5"], "server-websocket");
+        assert_eq!(first["menu"]["mods"]["num"], 0);
+    }
+
+    // every tick is sent whole, patches only when something changed
+    assert!(next(&mut full).unwrap().is_object());
+    set_timeout(&mut patches, Duration::from_millis(200));
+    assert_eq!(next(&mut patches), None);
+
+    world.set_mods(GameModsLegacy::HardRock);
+    set_timeout(&mut patches, Duration::from_secs(5));
+    let patch = next(&mut patches).unwrap();
+    let operations = patch.as_array().expect("a JSON patch");
+    assert!(operations
+        .iter()
+        .any(|operation| operation["path"] == "/menu/mods/num" && operation["value"] == 16));
+
+    let started = Instant::now();
+    loop {
+        let message = next(&mut full).unwrap();
+        if message["menu"]["mods"]["num"] == 16 {
+            break;
+        }
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
+
+#[test]
+fn unknown_websocket_path_is_rejected() {
+    let world = World::new("unknown");
+    let address = world.serve();
+    let stream = TcpStream::connect(address).unwrap();
+    let request = format!("ws://{address}/nope");
+    assert!(tungstenite::client(request, stream).is_err());
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "testing")]
+
+use std::sync::Mutex;
+
+use rosu_mem::signature::Signature;
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::memory::session::{SessionRecorder, SessionReplay};
+use rosu_memory_lib::reader::offsets::{set_offsets, OffsetTable};
+use rosu_memory_lib::reader::structs::{StaticAddresses, SIGNATURES};
+use rosu_memory_lib::{Error, MemorySource};
+
+/// Fake process that can still be written to while a recorder reads it
+struct Live(Mutex<FakeProcess>);
+
+impl MemorySource for Live {
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error> {
+        self.0.lock().unwrap().read(addr, len, buff)
+    }
+
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error> {
+        self.0.lock().unwrap().read_signature(sign)
+    }
+}
+
+fn session_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "rosu-memory-lib-{name}-{}.rmls",
+        std::process::id()
+    ))
+}
+
+#[test]
+fn signatures_match_regardless_of_formatting() {
+    let mut table = OffsetTable::default();
+    table.signatures.status_sig = SIGNATURES.status_sig.to_lowercase().into();
+    set_offsets(table).unwrap();
+
+    let sig: Signature = SIGNATURES.status_sig.parse().unwrap();
+    let mut fake = FakeProcess::new();
+    let placed = fake.place_signatures();
+    let recorder = SessionRecorder::new(&fake, &StaticAddresses::default());
+    assert_eq!(recorder.read_signature(&sig).unwrap(), placed.status);
+
+    let path = session_path("signatures");
+    recorder.save(&path).unwrap();
+    let replay = SessionReplay::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(replay.read_signature(&sig).unwrap(), placed.status);
+
+    set_offsets(OffsetTable::default()).unwrap();
+}
+
+#[test]
+fn newest_capture_wins() {
+    let mut fake = FakeProcess::new();
+    let addr = fake.alloc(0x10);
+    let live = Live(Mutex::new(fake));
+    let recorder = SessionRecorder::new(&live, &StaticAddresses::default());
+    let mut buff = [0u8; 0x10];
+
+    // frame 0: the whole block, frame 1: only a field inside of it
+    live.0.lock().unwrap().write_i32(addr + 4, 1);
+    recorder.read(addr, 0x10, &mut buff).unwrap();
+    recorder.next_frame();
+    live.0.lock().unwrap().write_i32(addr + 4, 2);
+    recorder.read_i32(addr + 4).unwrap();
+    recorder.next_frame();
+    // frame 2: the whole block again, the field capture is now stale
+    live.0.lock().unwrap().write_i32(addr + 4, 3);
+    recorder.read(addr, 0x10, &mut buff).unwrap();
+    recorder.next_frame();
+
+    let path = session_path("overlap");
+    recorder.save(&path).unwrap();
+    let mut replay = SessionReplay::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(replay.read_i32(addr + 4).unwrap(), 1);
+    assert!(replay.next_frame());
+    assert_eq!(replay.read_i32(addr + 4).unwrap(), 2);
+    assert!(replay.next_frame());
+    assert_eq!(replay.read_i32(addr + 4).unwrap(), 3);
+    replay.seek(1);
+    assert_eq!(replay.read_i32(addr + 4).unwrap(), 2);
+    assert!(replay.read_i32(addr + 0x10).is_err());
+}
+
+#[test]
+fn oversized_counts_fail_instead_of_allocating() {
+    let mut fake = FakeProcess::new();
+    let addr = fake.alloc(0x10);
+    let recorder = SessionRecorder::new(&fake, &StaticAddresses::default());
+    recorder.read_i32(addr).unwrap();
+    recorder.next_frame();
+    let path = session_path("oversized");
+    recorder.save(&path).unwrap();
+    let recorded = std::fs::read(&path).unwrap();
+
+    // magic, version, one address per signature, then the frame count
+    let frame_count = 8 + SIGNATURES.entries().len() * 4;
+    let mut corrupted = recorded.clone();
+    corrupted[frame_count..frame_count + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    std::fs::write(&path, &corrupted).unwrap();
+    assert!(SessionReplay::load(&path).is_err());
+
+    // frame timestamp and chunk count, then the address and length
+    let chunk_len = frame_count + 4 + 12 + 4;
+    let mut corrupted = recorded;
+    corrupted[chunk_len..chunk_len + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    std::fs::write(&path, &corrupted).unwrap();
+    assert!(SessionReplay::load(&path).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,185 @@
+#![cfg(feature = "testing")]
+
+use std::path::PathBuf;
+
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::beatmap::stable::offset::BEATMAP_OFFSET;
+use rosu_memory_lib::reader::beatmap::BeatmapReader;
+use rosu_memory_lib::reader::common::stable::offset::COMMON_OFFSET;
+use rosu_memory_lib::reader::common::{GameMode, GameState, OsuClientKind};
+use rosu_memory_lib::reader::snapshot::OsuSnapshot;
+use rosu_memory_lib::reader::structs::{State, StaticAddresses};
+use rosu_mods::GameModsLegacy;
+use rosu_pp::model::mode::GameMode as PpMode;
+use rosu_pp::{Beatmap, Difficulty};
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+100,100,1250,1,0,0:0:0:0:
+400,300,1500,1,0,0:0:0:0:
+256,192,2000,2,0,L|356:192,1,100
+50,350,2500,1,0,0:0:0:0:
+450,50,3000,1,0,0:0:0:0:
+";
+
+/// Song select with [`OSU_FILE`] selected, `mods` and `ruleset` are where the
+/// menu mods and ruleset are read from
+struct World {
+    dir: PathBuf,
+    fake: FakeProcess,
+    addresses: StaticAddresses,
+    mods: i32,
+    ruleset: i32,
+}
+
+impl World {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "rosu-memory-lib-star-rating-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("Songs").join("1 Test")).unwrap();
+        std::fs::write(dir.join("Songs").join("1 Test").join("test.osu"), OSU_FILE).unwrap();
+
+        let mut fake = FakeProcess::new().with_executable_dir(&dir);
+        let addresses = fake.place_signatures();
+        let status = fake.pointer_chain(addresses.status, &[-COMMON_OFFSET.status]);
+        fake.write_u32(status, GameState::SongSelect as u32);
+        let mods = fake.pointer_chain(addresses.menu_mods, &[COMMON_OFFSET.mods_ptr]);
+        let ruleset = fake.pointer_chain(addresses.base, &[COMMON_OFFSET.ruleset_ptr]);
+        let settings = fake.pointer_chain(
+            addresses.settings,
+            &[COMMON_OFFSET.settings_ptr, COMMON_OFFSET.settings_addr],
+        );
+        fake.write_string(settings + COMMON_OFFSET.path, "Songs");
+
+        let beatmap = fake.pointer_chain(addresses.base, &[-BEATMAP_OFFSET.ptr, 0]);
+        let metadata = &BEATMAP_OFFSET.metadata;
+        for offset in [
+            metadata.author,
+            metadata.creator,
+            metadata.title_romanized,
+            metadata.title_original,
+            metadata.difficulty,
+            metadata.tags,
+            BEATMAP_OFFSET.location.audio,
+            BEATMAP_OFFSET.location.cover,
+        ] {
+            fake.write_string(beatmap + offset, "");
+        }
+        fake.write_string(beatmap + BEATMAP_OFFSET.technical.md5, name);
+        fake.write_string(beatmap + BEATMAP_OFFSET.location.folder, "1 Test");
+        fake.write_string(beatmap + BEATMAP_OFFSET.location.filename, "test.osu");
+
+        Self {
+            dir,
+            fake,
+            addresses,
+            mods,
+            ruleset,
+        }
+    }
+
+    fn state(&self) -> State {
+        State {
+            addresses: self.addresses.clone(),
+        }
+    }
+}
+
+impl Drop for World {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn stars(mode: PpMode, mods: GameModsLegacy) -> f64 {
+    let mut map = Beatmap::from_bytes(OSU_FILE.as_bytes()).unwrap();
+    if mode != PpMode::Osu {
+        map.convert_mut(mode, &mods.into()).unwrap();
+    }
+    Difficulty::new()
+        .mods(mods)
+        .lazer(false)
+        .calculate(&map)
+        .stars()
+}
+
+#[test]
+fn snapshot_follows_menu_mods_and_ruleset() {
+    let mut world = World::new("snapshot");
+    let mut state = world.state();
+
+    let mut snapshot = OsuSnapshot::read(&world.fake, &mut state).unwrap();
+    let star_rating = &snapshot.beatmap.as_ref().unwrap().stats.star_rating;
+    assert_eq!(
+        star_rating.selected,
+        stars(PpMode::Osu, GameModsLegacy::NoMod)
+    );
+    assert_eq!(
+        star_rating.dt,
+        stars(PpMode::Osu, GameModsLegacy::DoubleTime)
+    );
+
+    // same beatmap, the cached info is kept but not its star rating
+    world
+        .fake
+        .write_u32(world.mods, GameModsLegacy::HardRock.bits());
+    snapshot.refresh(&world.fake, &mut state).unwrap();
+    let star_rating = &snapshot.beatmap.as_ref().unwrap().stats.star_rating;
+    assert_eq!(
+        star_rating.selected,
+        stars(PpMode::Osu, GameModsLegacy::HardRock)
+    );
+
+    world.fake.write_i32(world.ruleset, GameMode::Mania as i32);
+    snapshot.refresh(&world.fake, &mut state).unwrap();
+    let star_rating = &snapshot.beatmap.as_ref().unwrap().stats.star_rating;
+    assert_eq!(
+        star_rating.no_mod,
+        stars(PpMode::Mania, GameModsLegacy::NoMod)
+    );
+    assert_eq!(
+        star_rating.selected,
+        stars(PpMode::Mania, GameModsLegacy::HardRock)
+    );
+    assert_ne!(
+        star_rating.no_mod,
+        stars(PpMode::Osu, GameModsLegacy::NoMod)
+    );
+}
+
+#[test]
+fn selected_is_no_mod_without_menu_mods() {
+    let mut world = World::new("no-menu-mods");
+    // the menu mods pointer leads nowhere
+    world
+        .fake
+        .write_i32(world.addresses.menu_mods + COMMON_OFFSET.mods_ptr, 0);
+    let mut state = world.state();
+
+    let mut reader = BeatmapReader::new(&world.fake, &mut state, OsuClientKind::Stable).unwrap();
+    let star_rating = reader.info().unwrap().stats.star_rating;
+    assert_eq!(
+        star_rating.selected,
+        stars(PpMode::Osu, GameModsLegacy::NoMod)
+    );
+    assert!(reader.stats().is_ok());
+}
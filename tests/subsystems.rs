@@ -0,0 +1,209 @@
+#![cfg(feature = "testing")]
+
+use std::path::{Path, PathBuf};
+
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::beatmap::stable::offset::BEATMAP_OFFSET;
+use rosu_memory_lib::reader::beatmap::BeatmapReader;
+use rosu_memory_lib::reader::common::stable::memory::{game_time, is_paused, is_watching_replay};
+use rosu_memory_lib::reader::common::stable::offset::COMMON_OFFSET;
+use rosu_memory_lib::reader::common::{CommonReader, GameState, OsuClientKind};
+use rosu_memory_lib::reader::gameplay::stable::offset::GAMEPLAY_OFFSET;
+use rosu_memory_lib::reader::gameplay::GameplayReader;
+use rosu_memory_lib::reader::overlay::stable::offset::KEY_OVERLAY_OFFSET;
+use rosu_memory_lib::reader::overlay::OverlayReader;
+use rosu_memory_lib::reader::resultscreen::stable::offset::RESULT_SCREEN_OFFSET;
+use rosu_memory_lib::reader::resultscreen::ResultScreenReader;
+use rosu_memory_lib::reader::structs::{
+    SignatureName, SignatureStatus, State, StaticAddresses, Subsystem,
+};
+use rosu_memory_lib::reader::user::stable::offset::USER_PROFILE_OFFSET;
+use rosu_memory_lib::reader::user::UserReader;
+use rosu_memory_lib::Error;
+use rosu_memory_lib::MemorySource;
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,1500,1,0,0:0:0:0:
+";
+
+fn install() -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("rosu-memory-lib-subsystems-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("Songs").join("1 Test")).unwrap();
+    std::fs::write(dir.join("Songs").join("1 Test").join("test.osu"), OSU_FILE).unwrap();
+    dir
+}
+
+/// Every subsystem readable at once, apart from the game state, with where
+/// each signature was placed
+fn world(dir: &Path, game_state: GameState) -> (FakeProcess, StaticAddresses) {
+    let mut fake = FakeProcess::new().with_executable_dir(dir);
+    let addresses = fake.place_signatures();
+
+    let common = &COMMON_OFFSET;
+    let status = fake.pointer_chain(addresses.status, &[-common.status]);
+    fake.write_u32(status, game_state as u32);
+    fake.pointer_chain(addresses.menu_mods, &[common.mods_ptr]);
+    fake.pointer_chain(addresses.base, &[common.ruleset_ptr]);
+    fake.pointer_chain(addresses.base, &[common.pause_ptr]);
+    fake.pointer_chain(addresses.playtime, &[common.ig_time]);
+    fake.pointer_chain(addresses.check_replay_addr, &[common.check_replay]);
+    let settings = fake.pointer_chain(
+        addresses.settings,
+        &[common.settings_ptr, common.settings_addr],
+    );
+    fake.write_string(settings + common.path, "Songs");
+
+    let beatmap = fake.pointer_chain(addresses.base, &[-BEATMAP_OFFSET.ptr, 0]);
+    let metadata = &BEATMAP_OFFSET.metadata;
+    for offset in [
+        metadata.author,
+        metadata.creator,
+        metadata.title_romanized,
+        metadata.title_original,
+        metadata.difficulty,
+        metadata.tags,
+        BEATMAP_OFFSET.location.audio,
+        BEATMAP_OFFSET.location.cover,
+    ] {
+        fake.write_string(beatmap + offset, "");
+    }
+    fake.write_string(beatmap + BEATMAP_OFFSET.technical.md5, "subsystems");
+    fake.write_string(beatmap + BEATMAP_OFFSET.location.folder, "1 Test");
+    fake.write_string(beatmap + BEATMAP_OFFSET.location.filename, "test.osu");
+
+    let gameplay = &GAMEPLAY_OFFSET;
+    let gameplay_base = fake.pointer_chain(
+        addresses.rulesets,
+        &[-gameplay.ptr, gameplay.addr, gameplay.base],
+    );
+    let score_base = fake.pointer_chain(gameplay_base, &[gameplay.score_base]);
+    fake.pointer_chain(gameplay_base, &[gameplay.hp_base]);
+    fake.pointer_chain(score_base, &[gameplay.mods]);
+    fake.write_string(score_base + gameplay.username, "someone");
+    fake.pointer_chain(addresses.base, &[-gameplay.ruleset]);
+
+    let result_screen = &RESULT_SCREEN_OFFSET;
+    let result_base = fake.pointer_chain(
+        addresses.rulesets,
+        &[-result_screen.ptr, result_screen.addr, result_screen.base],
+    );
+    fake.pointer_chain(result_base, &[result_screen.mods]);
+    fake.write_string(result_base + result_screen.username, "someone");
+    fake.write_i16(result_base + result_screen.hits._300, 2);
+
+    let overlay = &KEY_OVERLAY_OFFSET;
+    let keys = fake.pointer_chain(
+        addresses.rulesets,
+        &[
+            -overlay.ptr,
+            overlay.addr,
+            overlay.base,
+            overlay.container,
+            overlay.array,
+        ],
+    );
+    fake.write_i32(keys + overlay.size, 4);
+    for slot in [
+        overlay.keys.key_1,
+        overlay.keys.key_2,
+        overlay.keys.mouse_1,
+        overlay.keys.mouse_2,
+    ] {
+        fake.pointer_chain(keys, &[slot]);
+    }
+
+    let user = fake.pointer_chain(addresses.user_profile, &[USER_PROFILE_OFFSET.ptr, 0]);
+    fake.write_string(user + USER_PROFILE_OFFSET.username, "someone");
+
+    (fake, addresses)
+}
+
+/// Uses every reader of `subsystem`
+fn read(fake: &FakeProcess, subsystem: Subsystem) -> Result<(), Error> {
+    let mut state = State::default();
+    let kind = OsuClientKind::Stable;
+    match subsystem {
+        Subsystem::Common => {
+            let mut reader = CommonReader::new(fake, &mut state, kind);
+            reader.game_state()?;
+            reader.menu_mods()?;
+            reader.menu_ruleset()?;
+            reader.path_folder()?;
+            game_time(fake, &mut state)?;
+            is_watching_replay(fake, &mut state)?;
+            is_paused(fake, &mut state)?;
+        }
+        Subsystem::Beatmap => {
+            let mut reader = BeatmapReader::new(fake, &mut state, kind)?;
+            reader.info()?;
+            reader.modded_stats()?;
+        }
+        Subsystem::Gameplay => {
+            GameplayReader::new(fake, &mut state, kind).info()?;
+        }
+        Subsystem::ResultScreen => {
+            let mut reader = ResultScreenReader::new(fake, &mut state, kind);
+            reader.info()?;
+            reader.pp()?;
+        }
+        Subsystem::User => {
+            UserReader::new(fake, &mut state, kind).info()?;
+        }
+        Subsystem::Overlay => {
+            OverlayReader::new(fake, &mut state, kind).key_overlay()?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn subsystems_list_the_signatures_their_readers_need() {
+    let dir = install();
+    for subsystem in Subsystem::ALL {
+        let game_state = match subsystem {
+            Subsystem::ResultScreen => GameState::ResultScreen,
+            _ => GameState::Playing,
+        };
+        if let Err(e) = read(&world(&dir, game_state).0, subsystem) {
+            panic!("{subsystem:?} can't be read with every signature: {e}");
+        }
+
+        for name in SignatureName::ALL {
+            // the pattern can't be found anymore
+            let (mut fake, placed) = world(&dir, game_state);
+            let SignatureStatus::Resolved(addr) = placed.status_of(name) else {
+                unreachable!("every signature is placed")
+            };
+            let byte = fake.read_u8(addr).unwrap();
+            fake.write_u8(addr, !byte);
+
+            let needed = read(&fake, subsystem).is_err();
+            assert_eq!(
+                needed,
+                subsystem.signatures().contains(&name),
+                "{subsystem:?} {} {name}",
+                if needed { "needs" } else { "doesn't need" },
+            );
+        }
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "testing")]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rosu_mem::signature::Signature;
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::common::stable::offset::COMMON_OFFSET;
+use rosu_memory_lib::reader::common::GameState;
+use rosu_memory_lib::reader::structs::State;
+use rosu_memory_lib::reader::supervisor::{ConnectionEvent, DetachReason, Supervisor};
+use rosu_memory_lib::{Error, MemorySource};
+
+/// Fake osu! whose process can be killed from the test
+struct Osu {
+    fake: FakeProcess,
+    alive: Arc<AtomicBool>,
+}
+
+impl MemorySource for Osu {
+    fn read(&self, addr: i32, len: usize, buff: &mut [u8]) -> Result<(), Error> {
+        self.fake.read(addr, len, buff)
+    }
+
+    fn read_signature(&self, sign: &Signature) -> Result<i32, Error> {
+        self.fake.read_signature(sign)
+    }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::SeqCst)
+    }
+}
+
+fn supervisor(game_state: GameState, alive: Arc<AtomicBool>) -> Supervisor<Osu> {
+    Supervisor::with_attach(move || {
+        let mut fake = FakeProcess::new();
+        let addresses = fake.place_signatures();
+        let status = fake.pointer_chain(addresses.status, &[-COMMON_OFFSET.status]);
+        fake.write_u32(status, game_state as u32);
+        let osu = Osu {
+            fake,
+            alive: alive.clone(),
+        };
+        Ok((State { addresses }, osu))
+    })
+    .check_interval(Duration::ZERO)
+}
+
+#[test]
+fn detects_game_exit_while_reads_succeed() {
+    let mut supervisor = supervisor(GameState::Exit, Arc::new(AtomicBool::new(true)));
+    let events = supervisor.subscribe();
+
+    assert!(supervisor.read(|s| s.common().game_state()).is_ok());
+    assert!(supervisor.read(|s| s.common().game_state()).is_ok());
+    assert_eq!(
+        events.try_iter().collect::<Vec<_>>(),
+        [
+            ConnectionEvent::Attached,
+            ConnectionEvent::Detached(DetachReason::GameExit),
+            ConnectionEvent::Attached,
+        ]
+    );
+}
+
+#[test]
+fn detects_process_exit_while_reads_succeed() {
+    let alive = Arc::new(AtomicBool::new(true));
+    let mut supervisor = supervisor(GameState::Playing, alive.clone());
+    let events = supervisor.subscribe();
+
+    assert!(supervisor.read(|s| s.common().game_state()).is_ok());
+    alive.store(false, Ordering::SeqCst);
+    assert!(supervisor.read(|s| s.common().game_state()).is_ok());
+    assert_eq!(
+        events.try_iter().take(2).collect::<Vec<_>>(),
+        [
+            ConnectionEvent::Attached,
+            ConnectionEvent::Detached(DetachReason::ProcessExited),
+        ]
+    );
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "testing")]
+
+use std::path::PathBuf;
+
+use rosu_memory_lib::memory::fake::FakeProcess;
+use rosu_memory_lib::reader::common::{CommonReader, OsuClientKind};
+use rosu_memory_lib::reader::structs::State;
+
+/// Where the resource section is loaded in the image
+const RSRC_RVA: u32 = 0x1000;
+
+/// Minimal PE with a single `.rsrc` section holding `info` as its version
+/// resource
+fn executable(info: &[u8]) -> Vec<u8> {
+    let mut resources = vec![0u8; 88];
+    let mut put = |offset: usize, value: u32| {
+        resources[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    };
+    // type directory -> RT_VERSION -> name directory
+    put(12, 1 << 16);
+    put(16, 16);
+    put(20, 0x8000_0000 | 24);
+    // name directory -> 1 -> language directory
+    put(24 + 12, 1 << 16);
+    put(24 + 16, 1);
+    put(24 + 20, 0x8000_0000 | 48);
+    // language directory -> en-US -> data entry
+    put(48 + 12, 1 << 16);
+    put(48 + 16, 0x409);
+    put(48 + 20, 72);
+    put(72, RSRC_RVA + 88);
+    put(76, info.len() as u32);
+    resources.extend_from_slice(info);
+
+    let mut exe = vec![0u8; 0x200];
+    exe[..2].copy_from_slice(b"MZ");
+    exe[0x3C..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+    exe[0x40..0x44].copy_from_slice(b"PE\0\0");
+    exe[0x46..0x48].copy_from_slice(&1u16.to_le_bytes());
+    exe[0x58..0x5E].copy_from_slice(b".rsrc\0");
+    exe[0x64..0x68].copy_from_slice(&RSRC_RVA.to_le_bytes());
+    exe[0x68..0x6C].copy_from_slice(&(resources.len() as u32).to_le_bytes());
+    exe[0x6C..0x70].copy_from_slice(&0x200u32.to_le_bytes());
+    exe.extend_from_slice(&resources);
+    exe
+}
+
+fn utf16(s: &str) -> Vec<u8> {
+    s.encode_utf16()
+        .chain([0])
+        .flat_map(u16::to_le_bytes)
+        .collect()
+}
+
+/// Version block with a `ProductVersion` string
+fn product_version(version: &str) -> Vec<u8> {
+    let mut info = vec![0u8; 6];
+    info.extend(utf16("ProductVersion"));
+    info.resize(info.len().next_multiple_of(4), 0);
+    info.extend(utf16(version));
+    info
+}
+
+/// Version block with only a `VS_FIXEDFILEINFO`, `2025.702.1.0`
+fn fixed_file_info() -> Vec<u8> {
+    let mut info = vec![0u8; 40];
+    for value in [0xFEEF_04BDu32, 0x0001_0000, (2025 << 16) | 702, 1 << 16] {
+        info.extend(value.to_le_bytes());
+    }
+    info
+}
+
+fn install(name: &str, exe: Option<Vec<u8>>) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "rosu-memory-lib-version-{name}-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("osu!.someone.cfg"),
+        "Foo = 1\nLastVersion = b20240101.1\n",
+    )
+    .unwrap();
+    if let Some(exe) = exe {
+        std::fs::write(dir.join("osu!.exe"), exe).unwrap();
+    }
+    dir
+}
+
+fn osu_version(dir: &PathBuf) -> String {
+    let fake = FakeProcess::new().with_executable_dir(dir);
+    let version = CommonReader::new(&fake, &mut State::default(), OsuClientKind::Stable)
+        .osu_version()
+        .unwrap()
+        .to_string();
+    std::fs::remove_dir_all(dir).unwrap();
+    version
+}
+
+#[test]
+fn executable_version_wins_over_config() {
+    let dir = install(
+        "strings",
+        Some(executable(&product_version("b20250702.1cuttingedge"))),
+    );
+    assert_eq!(osu_version(&dir), "b20250702.1cuttingedge");
+
+    let dir = install("fixed", Some(executable(&fixed_file_info())));
+    assert_eq!(osu_version(&dir), "b20250702.1");
+}
+
+#[test]
+fn falls_back_to_config() {
+    let dir = install("missing", None);
+    assert_eq!(osu_version(&dir), "b20240101.1");
+
+    let dir = install("not-pe", Some(b"hello".to_vec()));
+    assert_eq!(osu_version(&dir), "b20240101.1");
+
+    let dir = install("no-build", Some(executable(&product_version("1.0.0.0"))));
+    assert_eq!(osu_version(&dir), "b20240101.1");
+}
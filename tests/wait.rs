@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+use rosu_memory_lib::reader::wait::{CancellationToken, Waiter};
+use rosu_memory_lib::Error;
+
+#[test]
+fn cancel_interrupts_sleep() {
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        canceller.cancel();
+    });
+
+    let start = Instant::now();
+    let result = Waiter::new()
+        .interval(Duration::from_secs(60))
+        .cancel_token(token)
+        .until(|| Ok(None::<()>));
+    handle.join().unwrap();
+
+    assert!(matches!(result, Err(Error::Cancelled(_))));
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn invalid_backoff_factors() {
+    for factor in [-2.0, 0.0, f64::NAN, f64::INFINITY, 1e300] {
+        let mut polls = 0;
+        let value = Waiter::new()
+            .interval(Duration::from_millis(1))
+            .backoff(factor, Duration::from_millis(5))
+            .until(|| {
+                polls += 1;
+                Ok((polls == 4).then_some(polls))
+            })
+            .unwrap();
+        assert_eq!(value, 4);
+    }
+}